@@ -0,0 +1,78 @@
+//! Compares spawning with a collider built for every spawn against reusing the cached one, for
+//! each collider strategy. Run with `cargo bench --bench collider_spawn`.
+
+use avian3d::prelude::*;
+use bevy::{prelude::*, render::mesh::TorusMeshBuilder};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+#[path = "../src/cli.rs"]
+mod cli;
+#[expect(dead_code, reason = "Only the collider cache is benchmarked")]
+#[cfg_attr(
+    test,
+    expect(
+        unused_imports,
+        reason = "Its unit tests are left to the game's test target"
+    )
+)]
+#[path = "../src/game/collider_plugin.rs"]
+mod collider_plugin;
+
+use collider_plugin::{ColliderCache, ColliderStrategy};
+
+fn strategies() -> [(&'static str, ColliderStrategy); 4] {
+    [
+        ("convex_hull", ColliderStrategy::ConvexHull),
+        (
+            "convex_decomposition",
+            ColliderStrategy::ConvexDecomposition(VhacdParameters {
+                resolution: 32,
+                ..default()
+            }),
+        ),
+        (
+            "compound_primitives",
+            ColliderStrategy::CompoundPrimitives { slices: 4 },
+        ),
+        ("trimesh", ColliderStrategy::Trimesh),
+    ]
+}
+
+fn collider_spawn(criterion: &mut Criterion) {
+    let mut meshes = Assets::<Mesh>::default();
+    let mesh = meshes.add(TorusMeshBuilder::new(1.0, 2.0).build());
+
+    let mut group = criterion.benchmark_group("collider_spawn");
+    // Convex decomposition takes long enough that the default sample count would run for minutes
+    _ = group.sample_size(10);
+    for (name, strategy) in strategies() {
+        _ = group.bench_with_input(
+            BenchmarkId::new("uncached", name),
+            &strategy,
+            |bencher, strategy| {
+                bencher.iter(|| {
+                    ColliderCache::default()
+                        .get_or_build(&meshes, &mesh, Transform::IDENTITY, strategy)
+                        .expect("Torus should build a collider")
+                });
+            },
+        );
+
+        let mut cache = ColliderCache::default();
+        _ = group.bench_with_input(
+            BenchmarkId::new("cached", name),
+            &strategy,
+            |bencher, strategy| {
+                bencher.iter(|| {
+                    cache
+                        .get_or_build(&meshes, &mesh, Transform::IDENTITY, strategy)
+                        .expect("Torus should build a collider")
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, collider_spawn);
+criterion_main!(benches);
@@ -1,17 +1,40 @@
+use std::path::PathBuf;
+
 use derive_more::Display;
 
 use bevy::ecs::system::Resource;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+
+#[derive(ValueEnum, Display, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Singleplayer,
+    Multiplayer,
+}
+
+/// How ship colliders are built from the ship mesh.
+#[derive(ValueEnum, Display, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColliderKind {
+    ConvexHull,
+    ConvexDecomposition,
+    CompoundPrimitives,
+    /// only for static bodies, ships fall back to a box collider
+    Trimesh,
+}
 
 #[derive(Parser, Resource, Display, Clone)]
 #[display(
-    "Session Id: {}, Player Count: {}, Sync Test: {}",
+    "Mode: {}, Session Id: {}, Player Count: {}, Sync Test: {}",
+    mode.unwrap_or(GameMode::Singleplayer),
     session_id,
     player_count,
     synctest
 )]
 #[command(version, about, long_about = None)]
 pub struct CommandLineArguments {
+    /// whether to play alone or join the p2p session, implies --skip-menu
+    #[clap(long, value_enum)]
+    pub mode: Option<GameMode>,
+
     /// the session id for current p2p session
     #[clap(long, default_value = "spacerama")]
     pub session_id: String,
@@ -22,4 +45,32 @@ pub struct CommandLineArguments {
     /// runs the game in synctest mode
     #[clap(long)]
     pub synctest: bool,
+
+    /// skips the main menu and starts a new game straight away
+    #[clap(long)]
+    pub skip_menu: bool,
+
+    /// save file to resume from instead of starting a new game, implies --skip-menu
+    #[clap(long)]
+    pub load: Option<PathBuf>,
+
+    /// the number of players sharing this screen, each flying their own ship
+    #[clap(long, default_value = "1", value_parser = clap::value_parser!(u8).range(1..=4))]
+    pub local_players: u8,
+
+    /// records every input of the game to this file, to reproduce it later with --replay
+    #[clap(long)]
+    pub record: Option<PathBuf>,
+
+    /// plays a recording back instead of taking input, implies --skip-menu and ignores --load
+    #[clap(long)]
+    pub replay: Option<PathBuf>,
+
+    /// flies the first player's ship with a RON list of timed inputs instead of taking input
+    #[clap(long)]
+    pub flight_script: Option<PathBuf>,
+
+    /// how to build ship colliders, trades collision accuracy for loading time
+    #[clap(long, value_enum, default_value_t = ColliderKind::ConvexDecomposition)]
+    pub ship_collider: ColliderKind,
 }
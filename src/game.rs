@@ -1,5 +1,18 @@
+pub mod asteroid_plugin;
+pub mod collider_plugin;
+pub mod economy_plugin;
+pub mod exploration_plugin;
+pub mod flight_script_plugin;
+pub mod formation_plugin;
+pub mod inventory_plugin;
+pub mod loading_plugin;
+pub mod mission_plugin;
 pub mod network_plugin;
 pub mod physics_plugin;
 pub mod plugin_group;
+pub mod replay_plugin;
+pub mod save_plugin;
 pub mod ship_plugin;
 pub mod states_plugin;
+pub mod targeting_plugin;
+pub mod world_plugin;
@@ -0,0 +1,263 @@
+use core::f32::consts::TAU;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    inventory_plugin::{CargoHold, ItemAssets, ItemCatalog, ItemId},
+    ship_plugin::{heading, ActionEventData},
+    states_plugin::{FrameSystemsSet, MainState},
+    targeting_plugin::{Destroyed, Targetable},
+};
+
+#[derive(Debug)]
+pub struct AsteroidPlugin;
+
+impl Plugin for AsteroidPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app.add_systems(
+            FixedUpdate,
+            (fire_mining_lasers, fracture_depleted_asteroids)
+                .chain()
+                .in_set(FrameSystemsSet::Player),
+        );
+    }
+}
+
+/// A minable rock holding a pool of a single resource.
+#[derive(Component, Debug, Clone)]
+pub struct Asteroid {
+    pub resource: ItemId,
+    /// Units of `resource` left before the asteroid breaks apart.
+    pub remaining: f32,
+    /// Units of `resource` the asteroid started with, shared out between its fragments.
+    pub pool: f32,
+    pub radius: f32,
+    /// The last ship to fire a mining laser at this asteroid.
+    pub mined_by: Option<Entity>,
+}
+
+#[derive(Bundle)]
+pub struct AsteroidBundle {
+    asteroid: Asteroid,
+    name: Name,
+    spatial: SpatialBundle,
+    state_scoped: StateScoped<MainState>,
+    rigid_body: RigidBody,
+    collider: Collider,
+    linear_velocity: LinearVelocity,
+    targetable: Targetable,
+}
+
+impl AsteroidBundle {
+    pub fn new(resource: ItemId, radius: f32, transform: Transform) -> Self {
+        Self::fragment(
+            resource,
+            radius,
+            radius * ASTEROID_RESOURCE_PER_RADIUS,
+            transform,
+            Vec3::ZERO,
+        )
+    }
+
+    fn fragment(
+        resource: ItemId,
+        radius: f32,
+        pool: f32,
+        transform: Transform,
+        velocity: Vec3,
+    ) -> Self {
+        Self {
+            name: Name::new(format!("{resource} asteroid")),
+            asteroid: Asteroid {
+                resource,
+                remaining: pool,
+                pool,
+                radius,
+                mined_by: None,
+            },
+            spatial: SpatialBundle::from_transform(transform),
+            state_scoped: StateScoped(MainState::InGame),
+            rigid_body: RigidBody::Dynamic,
+            collider: Collider::sphere(radius),
+            linear_velocity: LinearVelocity(velocity),
+            targetable: Targetable,
+        }
+    }
+}
+
+/// Mining state of a ship's laser, put on every ship.
+#[derive(Component, Debug, Clone, Default)]
+pub struct MiningLaser {
+    /// Where the beam hit this tick, if it is firing at anything.
+    pub beam_end: Option<Vec3>,
+    /// Ore extracted but not yet a whole unit in the hold.
+    extracted: f32,
+    /// Which ore `extracted` is.
+    extracted_resource: Option<ItemId>,
+}
+
+impl MiningLaser {
+    /// Adds ore extracted from an asteroid, returning how many whole units are ready for the hold.
+    fn extract(&mut self, resource: &ItemId, amount: f32) -> u32 {
+        // Part of a unit of one ore can't make up a unit of another, the leftover is lost
+        if self.extracted_resource.as_ref() != Some(resource) {
+            self.extracted = 0.0;
+            self.extracted_resource = Some(resource.clone());
+        }
+        self.extracted += amount;
+
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "Extracted ore is positive and floored"
+        )]
+        let whole_units = self.extracted.floor() as u32;
+        whole_units
+    }
+
+    /// Takes units out of the extracted ore once they are in the hold.
+    fn deposit(&mut self, units: u32) {
+        #[expect(clippy::cast_precision_loss, reason = "Cargo quantities are small")]
+        let units = units as f32;
+        self.extracted -= units;
+    }
+}
+
+const ASTEROID_RESOURCE_PER_RADIUS: f32 = 5.0;
+/// Asteroids smaller than this crumble to dust instead of fracturing further.
+const ASTEROID_MIN_FRAGMENT_RADIUS: f32 = 2.0;
+const ASTEROID_FRAGMENT_COUNT: u8 = 3;
+const ASTEROID_FRAGMENT_SPEED: f32 = 3.0;
+
+const MINING_LASER_RANGE: f32 = 300.0;
+const MINING_LASER_RATE: f32 = 2.0; // units per second
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn fire_mining_lasers(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    item_assets: Res<ItemAssets>,
+    catalogs: Res<Assets<ItemCatalog>>,
+    mut ships: Query<(
+        Entity,
+        &Transform,
+        &ActionEventData,
+        &mut MiningLaser,
+        &mut CargoHold,
+    )>,
+    mut asteroids: Query<&mut Asteroid>,
+) {
+    let Some(catalog) = item_assets.catalog(&catalogs) else {
+        return;
+    };
+
+    for (ship, transform, action_event_data, mut mining_laser, mut cargo_hold) in &mut ships {
+        mining_laser.beam_end = None;
+        if action_event_data.mining_laser.abs() < 0.5 {
+            continue;
+        }
+
+        let direction = heading(transform);
+        let Some(hit) = spatial_query.cast_ray(
+            transform.translation,
+            direction,
+            MINING_LASER_RANGE,
+            true,
+            SpatialQueryFilter::from_excluded_entities([ship]),
+        ) else {
+            continue;
+        };
+        mining_laser.beam_end = Some(transform.translation + *direction * hit.time_of_impact);
+
+        let Ok(mut asteroid) = asteroids.get_mut(hit.entity) else {
+            continue;
+        };
+        let Ok(definition) = catalog.get(&asteroid.resource) else {
+            continue;
+        };
+        // A full hold can't take any more, so leave the rock alone
+        if cargo_hold.room_for(definition) == 0 {
+            continue;
+        }
+
+        asteroid.mined_by = Some(ship);
+        let extracted = (MINING_LASER_RATE * time.delta_seconds()).min(asteroid.remaining);
+        asteroid.remaining -= extracted;
+        let whole_units = mining_laser.extract(&asteroid.resource, extracted);
+        let deposited = whole_units.min(cargo_hold.room_for(definition));
+        if deposited > 0
+            && cargo_hold
+                .add(&asteroid.resource, deposited, catalog)
+                .is_ok()
+        {
+            mining_laser.deposit(deposited);
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn fracture_depleted_asteroids(
+    mut commands: Commands,
+    mut destroyed: EventWriter<Destroyed>,
+    asteroids: Query<(Entity, &Asteroid, &Name, &Transform, &LinearVelocity)>,
+) {
+    for (entity, asteroid, name, transform, velocity) in &asteroids {
+        if asteroid.remaining > 0.0 {
+            continue;
+        }
+
+        commands.entity(entity).despawn_recursive();
+        _ = destroyed.send(Destroyed {
+            entity,
+            name: Some(name.to_string()),
+            attacker: asteroid.mined_by,
+        });
+
+        let fragment_radius = asteroid.radius * 0.5;
+        if fragment_radius < ASTEROID_MIN_FRAGMENT_RADIUS {
+            continue;
+        }
+
+        // Fragments burst outwards evenly around the parent's local up axis, far enough apart
+        // that neighbours 120 degrees round just touch instead of overlapping
+        let spacing = asteroid.radius / 3_f32.sqrt();
+        let pool = asteroid.pool / f32::from(ASTEROID_FRAGMENT_COUNT);
+        for index in 0..ASTEROID_FRAGMENT_COUNT {
+            let angle = TAU * f32::from(index) / f32::from(ASTEROID_FRAGMENT_COUNT);
+            let direction = transform.rotation * Quat::from_rotation_y(angle) * Vec3::X;
+            let fragment_transform =
+                Transform::from_translation(transform.translation + direction * spacing)
+                    .with_rotation(transform.rotation);
+            _ = commands.spawn(AsteroidBundle::fragment(
+                asteroid.resource.clone(),
+                fragment_radius,
+                pool,
+                fragment_transform,
+                velocity.0 + direction * ASTEROID_FRAGMENT_SPEED,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leftover_ore_stays_with_its_resource() {
+        let iron = ItemId("iron_ore".to_owned());
+        let platinum = ItemId("platinum_ore".to_owned());
+        let mut mining_laser = MiningLaser::default();
+
+        assert_eq!(mining_laser.extract(&iron, 0.75), 0);
+        // Moving to another ore doesn't turn the iron left over into platinum
+        assert_eq!(mining_laser.extract(&platinum, 0.5), 0);
+        assert_eq!(mining_laser.extract(&platinum, 0.75), 1);
+        mining_laser.deposit(1);
+
+        // Nor does moving back turn the platinum into iron
+        assert_eq!(mining_laser.extract(&iron, 0.75), 0);
+        assert_eq!(mining_laser.extract(&iron, 0.25), 1);
+    }
+}
@@ -0,0 +1,294 @@
+use core::error::Error;
+
+use derive_more::Display;
+
+use avian3d::prelude::*;
+use bevy::{
+    prelude::*,
+    render::mesh::{PrimitiveTopology, VertexAttributeValues},
+};
+
+use crate::cli::ColliderKind;
+
+#[derive(Debug)]
+pub struct ColliderPlugin;
+
+impl Plugin for ColliderPlugin {
+    fn build(&self, app: &mut App) {
+        // Kept across sessions, the meshes don't change between games
+        _ = app.init_resource::<ColliderCache>();
+    }
+}
+
+/// How a collider is built from a mesh, from cheapest to most accurate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColliderStrategy {
+    /// A single convex hull around every vertex, cheap but fills in any concave parts.
+    ConvexHull,
+    /// Convex parts found by V-HACD, accurate for dynamic bodies but slow to compute.
+    ConvexDecomposition(VhacdParameters),
+    /// Boxes around slices of the mesh along its longest axis.
+    CompoundPrimitives { slices: u32 },
+    /// The triangles themselves, meant for static objects since they have no volume to collide with.
+    Trimesh,
+}
+
+impl ColliderStrategy {
+    /// A box around the whole mesh, which works for any mesh that has vertices.
+    pub const BOUNDING_BOX: Self = Self::CompoundPrimitives { slices: 1 };
+
+    /// Whether the collider can go on a body that moves, trimeshes have no mass or inside.
+    pub const fn supports_dynamic_bodies(&self) -> bool {
+        !matches!(self, Self::Trimesh)
+    }
+
+    pub fn build(&self, mesh: &Mesh) -> Result<Collider, ColliderError> {
+        match self {
+            Self::ConvexHull => {
+                validate_triangles(mesh)?;
+                Collider::convex_hull_from_mesh(mesh).ok_or(ColliderError::ConstructionFailed)
+            }
+            Self::ConvexDecomposition(parameters) => {
+                validate_triangles(mesh)?;
+                Collider::convex_decomposition_from_mesh_with_config(mesh, parameters)
+                    .ok_or(ColliderError::ConstructionFailed)
+            }
+            Self::CompoundPrimitives { slices } => compound_primitives(mesh, *slices),
+            Self::Trimesh => {
+                validate_triangles(mesh)?;
+                Collider::trimesh_from_mesh(mesh).ok_or(ColliderError::ConstructionFailed)
+            }
+        }
+    }
+}
+
+impl From<ColliderKind> for ColliderStrategy {
+    fn from(kind: ColliderKind) -> Self {
+        match kind {
+            ColliderKind::ConvexHull => Self::ConvexHull,
+            ColliderKind::ConvexDecomposition => Self::ConvexDecomposition(default()),
+            ColliderKind::CompoundPrimitives => Self::CompoundPrimitives { slices: 4 },
+            ColliderKind::Trimesh => Self::Trimesh,
+        }
+    }
+}
+
+/// Why a collider couldn't be built from a mesh.
+#[derive(Debug, Display)]
+pub enum ColliderError {
+    #[display("Mesh is not loaded")]
+    MissingMesh,
+    #[display("Mesh has no vertex positions")]
+    MissingPositions,
+    #[display("Mesh has no indices")]
+    MissingIndices,
+    #[display("Mesh uses {_0:?} topology, expected a triangle list")]
+    UnsupportedTopology(PrimitiveTopology),
+    #[display("Collider construction from the mesh failed")]
+    ConstructionFailed,
+    #[display("Trimesh colliders only work on static bodies")]
+    StaticOnly,
+}
+
+impl Error for ColliderError {}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ColliderKey {
+    mesh: AssetId<Mesh>,
+    /// Applied to the mesh before building, e.g. to line a model up with the ship's heading.
+    transform: Transform,
+    strategy: ColliderStrategy,
+}
+
+/// Colliders already built from meshes, cloning one only shares its shape.
+#[derive(Resource, Default)]
+pub struct ColliderCache {
+    // Only a handful of meshes get colliders, so a list beats hashing the float parameters
+    colliders: Vec<(ColliderKey, Collider)>,
+}
+
+impl ColliderCache {
+    pub fn get_or_build(
+        &mut self,
+        meshes: &Assets<Mesh>,
+        mesh: &Handle<Mesh>,
+        transform: Transform,
+        strategy: &ColliderStrategy,
+    ) -> Result<Collider, ColliderError> {
+        let key = ColliderKey {
+            mesh: mesh.id(),
+            transform,
+            strategy: strategy.clone(),
+        };
+        if let Some((_, collider)) = self.colliders.iter().find(|(cached, _)| *cached == key) {
+            return Ok(collider.clone());
+        }
+
+        let mesh = meshes
+            .get(mesh)
+            .ok_or(ColliderError::MissingMesh)?
+            .clone()
+            .transformed_by(transform);
+        let collider = strategy.build(&mesh)?;
+        self.colliders.push((key, collider.clone()));
+        Ok(collider)
+    }
+}
+
+fn validate_triangles(mesh: &Mesh) -> Result<(), ColliderError> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return Err(ColliderError::UnsupportedTopology(
+            mesh.primitive_topology(),
+        ));
+    }
+    if mesh.attribute(Mesh::ATTRIBUTE_POSITION).is_none() {
+        return Err(ColliderError::MissingPositions);
+    }
+    if mesh.indices().is_none() {
+        return Err(ColliderError::MissingIndices);
+    }
+    Ok(())
+}
+
+#[expect(clippy::cast_precision_loss, reason = "Slice counts are small")]
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "Slice index is clamped to the slice count"
+)]
+fn compound_primitives(mesh: &Mesh, slices: u32) -> Result<Collider, ColliderError> {
+    let positions = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .and_then(VertexAttributeValues::as_float3)
+        .filter(|positions| !positions.is_empty())
+        .ok_or(ColliderError::MissingPositions)?;
+
+    let (min, max) = positions.iter().map(|&position| Vec3::from(position)).fold(
+        (Vec3::INFINITY, Vec3::NEG_INFINITY),
+        |(min, max), position| (min.min(position), max.max(position)),
+    );
+    let size = max - min;
+    let axis = if size.x >= size.y && size.x >= size.z {
+        Vec3::X
+    } else if size.y >= size.z {
+        Vec3::Y
+    } else {
+        Vec3::Z
+    };
+
+    let slices = slices.max(1);
+    let slice_length = size.dot(axis) / slices as f32;
+    let mut bounds = vec![(Vec3::INFINITY, Vec3::NEG_INFINITY); slices as usize];
+    for &position in positions {
+        let position = Vec3::from(position);
+        let slice = if slice_length > 0.0 {
+            (((position - min).dot(axis) / slice_length) as usize).min(slices as usize - 1)
+        } else {
+            0
+        };
+        if let Some((slice_min, slice_max)) = bounds.get_mut(slice) {
+            *slice_min = slice_min.min(position);
+            *slice_max = slice_max.max(position);
+        }
+    }
+
+    let parts = bounds
+        .into_iter()
+        .filter(|(slice_min, slice_max)| slice_min.cmple(*slice_max).all())
+        .map(|(slice_min, slice_max)| {
+            let size = slice_max - slice_min;
+            (
+                (slice_min + slice_max) / 2.0,
+                Quat::IDENTITY,
+                Collider::cuboid(size.x, size.y, size.z),
+            )
+        })
+        .collect();
+    Ok(Collider::compound(parts))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bevy::render::mesh::TorusMeshBuilder;
+
+    use super::*;
+
+    fn strategies() -> [ColliderStrategy; 4] {
+        [
+            ColliderStrategy::ConvexHull,
+            ColliderStrategy::ConvexDecomposition(VhacdParameters {
+                resolution: 32,
+                ..default()
+            }),
+            ColliderStrategy::CompoundPrimitives { slices: 4 },
+            ColliderStrategy::Trimesh,
+        ]
+    }
+
+    #[test]
+    fn cache_reuses_colliders() {
+        let mut meshes = Assets::<Mesh>::default();
+        let mesh = meshes.add(TorusMeshBuilder::new(1.0, 2.0).build());
+
+        let mut cache = ColliderCache::default();
+        for strategy in strategies() {
+            let first = cache
+                .get_or_build(&meshes, &mesh, Transform::IDENTITY, &strategy)
+                .expect("Torus should build a collider");
+            let second = cache
+                .get_or_build(&meshes, &mesh, Transform::IDENTITY, &strategy)
+                .expect("Torus should build a collider");
+            assert!(Arc::ptr_eq(&first.shape().0, &second.shape().0));
+        }
+
+        let rotated = cache
+            .get_or_build(
+                &meshes,
+                &mesh,
+                Transform::from_rotation(Quat::from_rotation_x(1.0)),
+                &ColliderStrategy::ConvexHull,
+            )
+            .expect("Torus should build a collider");
+        let unrotated = cache
+            .get_or_build(
+                &meshes,
+                &mesh,
+                Transform::IDENTITY,
+                &ColliderStrategy::ConvexHull,
+            )
+            .expect("Torus should build a collider");
+        assert!(!Arc::ptr_eq(&rotated.shape().0, &unrotated.shape().0));
+    }
+
+    #[test]
+    fn bounding_box_covers_the_mesh() {
+        let mut meshes = Assets::<Mesh>::default();
+        let mesh = meshes.add(Cuboid::new(2.0, 4.0, 6.0));
+
+        let collider = ColliderCache::default()
+            .get_or_build(
+                &meshes,
+                &mesh,
+                Transform::from_xyz(1.0, 0.0, 0.0),
+                &ColliderStrategy::BOUNDING_BOX,
+            )
+            .expect("Cuboid should build a collider");
+        let aabb = collider.aabb(Vec3::ZERO, Quat::IDENTITY);
+        assert!(aabb.min.abs_diff_eq(Vec3::new(0.0, -2.0, -3.0), 0.001));
+        assert!(aabb.max.abs_diff_eq(Vec3::new(2.0, 2.0, 3.0), 0.001));
+    }
+
+    #[test]
+    fn missing_mesh_is_an_error() {
+        let meshes = Assets::<Mesh>::default();
+        let result = ColliderCache::default().get_or_build(
+            &meshes,
+            &Handle::default(),
+            Transform::IDENTITY,
+            &ColliderStrategy::BOUNDING_BOX,
+        );
+        assert!(matches!(result, Err(ColliderError::MissingMesh)));
+    }
+}
@@ -0,0 +1,600 @@
+use core::error::Error;
+use std::collections::BTreeMap;
+
+use derive_more::Display;
+use serde::Deserialize;
+
+use avian3d::prelude::*;
+
+use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+
+use super::{
+    inventory_plugin::{CargoError, CargoHold, ItemAssets, ItemCatalog, ItemId},
+    loading_plugin::LoadingAppExt,
+    ship_plugin::ActionEventData,
+    states_plugin::{reset_resource, FrameSystemsSet, MainState},
+    targeting_plugin::Targetable,
+};
+
+#[derive(Debug)]
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_plugins(RonAssetPlugin::<StationCatalog>::new(&["stations.ron"]))
+            .load_tracked_collection::<EconomyAssets>()
+            .init_resource::<EconomyClock>()
+            .add_event::<TradeRequest>()
+            .add_event::<TradeCompleted>()
+            .add_systems(OnEnter(MainState::InGame), spawn_stations)
+            .add_systems(OnExit(MainState::InGame), reset_resource::<EconomyClock>)
+            .add_systems(FixedUpdate, tick_markets.in_set(FrameSystemsSet::World))
+            .add_systems(
+                FixedUpdate,
+                (update_traders, request_trades, process_trades)
+                    .chain()
+                    .in_set(FrameSystemsSet::Player),
+            );
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+struct EconomyAssets {
+    #[asset(path = "economy/catalog.stations.ron")]
+    stations: Handle<StationCatalog>,
+}
+
+/// How a commodity behaves at one station, straight from the data files.
+#[derive(Deserialize, Clone, Debug)]
+pub struct CommodityDefinition {
+    /// Price in credits when supply matches demand.
+    pub base_price: u32,
+    pub base_supply: f32,
+    pub base_demand: f32,
+    /// Units the station makes every economy tick.
+    #[serde(default)]
+    pub production: f32,
+    /// Units the station uses up every economy tick.
+    #[serde(default)]
+    pub consumption: f32,
+    pub max_supply: f32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct StationDefinition {
+    pub name: String,
+    pub position: [f32; 3],
+    pub commodities: BTreeMap<ItemId, CommodityDefinition>,
+}
+
+/// Every station in the world, loaded from `assets/economy/*.stations.ron`.
+#[derive(Asset, TypePath, Deserialize, Clone, Debug)]
+pub struct StationCatalog {
+    pub stations: Vec<StationDefinition>,
+}
+
+/// Counts fixed updates so markets evolve on simulation ticks rather than wall time,
+/// keeping prices identical on every peer.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct EconomyClock {
+    pub tick: u64,
+}
+
+const ECONOMY_TICK_INTERVAL: u64 = 64;
+
+const MIN_SUPPLY: f32 = 1.0;
+const MIN_PRICE_FACTOR: f32 = 0.25;
+const MAX_PRICE_FACTOR: f32 = 4.0;
+/// Fraction of the gap to base demand closed every economy tick.
+const DEMAND_RELAXATION: f32 = 0.05;
+/// Stations buy for less than they sell.
+const SELL_PRICE_FACTOR: f32 = 0.9;
+
+#[derive(Debug, Clone)]
+pub struct CommodityMarket {
+    definition: CommodityDefinition,
+    supply: f32,
+    demand: f32,
+    price: u32,
+}
+
+impl CommodityMarket {
+    fn new(definition: &CommodityDefinition) -> Self {
+        let mut commodity = Self {
+            definition: definition.clone(),
+            supply: definition.base_supply,
+            demand: definition.base_demand,
+            price: 0,
+        };
+        commodity.update_price();
+        commodity
+    }
+
+    pub const fn supply(&self) -> f32 {
+        self.supply
+    }
+
+    /// Credits per unit the station charges when the player buys.
+    pub const fn buy_price(&self) -> u32 {
+        self.price
+    }
+
+    /// Credits per unit the station pays when the player sells.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss,
+        reason = "Prices are small positive values"
+    )]
+    pub fn sell_price(&self) -> u32 {
+        (self.price as f32 * SELL_PRICE_FACTOR).floor() as u32
+    }
+
+    fn tick(&mut self) {
+        self.supply = (self.supply + self.definition.production - self.definition.consumption)
+            .clamp(MIN_SUPPLY, self.definition.max_supply);
+        self.demand += (self.definition.base_demand - self.demand) * DEMAND_RELAXATION;
+        self.update_price();
+    }
+
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss,
+        reason = "Prices are small positive values"
+    )]
+    fn update_price(&mut self) {
+        // Only basic arithmetic so every platform rounds the same way
+        let factor =
+            (self.demand / self.supply.max(MIN_SUPPLY)).clamp(MIN_PRICE_FACTOR, MAX_PRICE_FACTOR);
+        self.price = (self.definition.base_price as f32 * factor)
+            .round()
+            .max(1.0) as u32;
+    }
+}
+
+/// A station's market, one entry per commodity it trades.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Market {
+    commodities: BTreeMap<ItemId, CommodityMarket>,
+}
+
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum TradeError {
+    #[display("The station doesn't trade {_0}")]
+    UnknownCommodity(ItemId),
+    #[display("Need {needed} credits but only have {available}")]
+    NotEnoughCredits { needed: u64, available: u64 },
+    #[display("The station only has {available} {item}")]
+    NotEnoughStock { item: ItemId, available: u32 },
+    #[display("{_0}")]
+    Cargo(CargoError),
+}
+
+impl Error for TradeError {}
+
+impl From<CargoError> for TradeError {
+    fn from(error: CargoError) -> Self {
+        Self::Cargo(error)
+    }
+}
+
+impl Market {
+    pub fn new(commodities: &BTreeMap<ItemId, CommodityDefinition>) -> Self {
+        Self {
+            commodities: commodities
+                .iter()
+                .map(|(item, definition)| (item.clone(), CommodityMarket::new(definition)))
+                .collect(),
+        }
+    }
+
+    pub fn commodities(&self) -> impl Iterator<Item = (&ItemId, &CommodityMarket)> {
+        self.commodities.iter()
+    }
+
+    /// Advances supply, demand and prices by one economy tick.
+    pub fn tick(&mut self) {
+        for commodity in self.commodities.values_mut() {
+            commodity.tick();
+        }
+    }
+
+    /// Moves `quantity` units from the station into `cargo_hold`, returning what it cost.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "Supply is never negative"
+    )]
+    pub fn buy(
+        &mut self,
+        item: &ItemId,
+        quantity: u32,
+        credits: &mut Credits,
+        cargo_hold: &mut CargoHold,
+        catalog: &ItemCatalog,
+    ) -> Result<u64, TradeError> {
+        let commodity = self
+            .commodities
+            .get_mut(item)
+            .ok_or_else(|| TradeError::UnknownCommodity(item.clone()))?;
+
+        let available = commodity.supply.floor() as u32;
+        if available < quantity {
+            return Err(TradeError::NotEnoughStock {
+                item: item.clone(),
+                available,
+            });
+        }
+
+        let cost = u64::from(commodity.buy_price()) * u64::from(quantity);
+        if credits.0 < cost {
+            return Err(TradeError::NotEnoughCredits {
+                needed: cost,
+                available: credits.0,
+            });
+        }
+
+        cargo_hold.add(item, quantity, catalog)?;
+        credits.0 -= cost;
+        #[expect(clippy::cast_precision_loss, reason = "Cargo quantities are small")]
+        let quantity = quantity as f32;
+        commodity.supply -= quantity;
+        commodity.demand += quantity;
+        commodity.update_price();
+        Ok(cost)
+    }
+
+    /// Moves `quantity` units from `cargo_hold` to the station, returning what it paid.
+    pub fn sell(
+        &mut self,
+        item: &ItemId,
+        quantity: u32,
+        credits: &mut Credits,
+        cargo_hold: &mut CargoHold,
+        catalog: &ItemCatalog,
+    ) -> Result<u64, TradeError> {
+        let commodity = self
+            .commodities
+            .get_mut(item)
+            .ok_or_else(|| TradeError::UnknownCommodity(item.clone()))?;
+
+        cargo_hold.remove(item, quantity, catalog)?;
+        let payment = u64::from(commodity.sell_price()) * u64::from(quantity);
+        credits.0 += payment;
+        #[expect(clippy::cast_precision_loss, reason = "Cargo quantities are small")]
+        let quantity = quantity as f32;
+        commodity.supply = (commodity.supply + quantity).min(commodity.definition.max_supply);
+        commodity.demand = (commodity.demand - quantity).max(0.0);
+        commodity.update_price();
+        Ok(payment)
+    }
+}
+
+/// A player's balance, kept on their ship.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Credits(pub u64);
+
+pub const STARTING_CREDITS: u64 = 1_000;
+
+#[derive(Component, Debug, Clone)]
+pub struct Station {
+    pub name: String,
+}
+
+#[derive(Bundle)]
+pub struct StationBundle {
+    station: Station,
+    name: Name,
+    market: Market,
+    spatial: SpatialBundle,
+    state_scoped: StateScoped<MainState>,
+    rigid_body: RigidBody,
+    collider: Collider,
+    targetable: Targetable,
+}
+
+pub const STATION_SIZE: f32 = 40.0;
+
+impl StationBundle {
+    pub fn new(definition: &StationDefinition) -> Self {
+        let [x, y, z] = definition.position;
+        Self {
+            station: Station {
+                name: definition.name.clone(),
+            },
+            name: Name::new(definition.name.clone()),
+            market: Market::new(&definition.commodities),
+            spatial: SpatialBundle::from_transform(Transform::from_xyz(x, y, z)),
+            state_scoped: StateScoped(MainState::InGame),
+            rigid_body: RigidBody::Static,
+            collider: Collider::cuboid(STATION_SIZE, STATION_SIZE, STATION_SIZE),
+            targetable: Targetable,
+        }
+    }
+}
+
+/// Which station the ship is close enough to trade with and the commodity picked on its market.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Trader {
+    pub station: Option<Entity>,
+    pub selected: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeKind {
+    Buy,
+    Sell,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct TradeRequest {
+    pub ship: Entity,
+    pub station: Entity,
+    pub item: ItemId,
+    pub quantity: u32,
+    pub kind: TradeKind,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct TradeCompleted {
+    pub request: TradeRequest,
+    pub credits: u64,
+}
+
+const TRADE_RANGE: f32 = 150.0;
+const TRADE_BATCH: u32 = 10;
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn spawn_stations(
+    mut commands: Commands,
+    economy_assets: Res<EconomyAssets>,
+    catalogs: Res<Assets<StationCatalog>>,
+) {
+    let Some(catalog) = catalogs.get(&economy_assets.stations) else {
+        warn!("Station catalog is not loaded, the world has no stations");
+        return;
+    };
+
+    for definition in &catalog.stations {
+        _ = commands.spawn(StationBundle::new(definition));
+    }
+}
+
+fn tick_markets(mut clock: ResMut<EconomyClock>, mut markets: Query<&mut Market>) {
+    clock.tick += 1;
+    if !clock.tick.is_multiple_of(ECONOMY_TICK_INTERVAL) {
+        return;
+    }
+
+    for mut market in &mut markets {
+        market.tick();
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_traders(
+    mut traders: Query<(&Transform, &ActionEventData, &mut Trader)>,
+    stations: Query<(Entity, &Transform, &Market)>,
+) {
+    for (transform, action_event_data, mut trader) in &mut traders {
+        let nearest = stations
+            .iter()
+            .map(|(entity, station_transform, market)| {
+                let distance = transform
+                    .translation
+                    .distance(station_transform.translation);
+                (entity, distance, market)
+            })
+            .filter(|&(_, distance, _)| distance <= TRADE_RANGE)
+            .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b));
+
+        let Some((station, _, market)) = nearest else {
+            *trader = Trader::default();
+            continue;
+        };
+
+        if trader.station != Some(station) {
+            *trader = Trader {
+                station: Some(station),
+                selected: 0,
+            };
+        }
+        if action_event_data.market_next.abs() > 0.5 && !market.commodities.is_empty() {
+            trader.selected = (trader.selected + 1) % market.commodities.len();
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn request_trades(
+    traders: Query<(Entity, &ActionEventData, &Trader, &CargoHold)>,
+    markets: Query<&Market>,
+    mut trade_requests: EventWriter<TradeRequest>,
+) {
+    for (ship, action_event_data, trader, cargo_hold) in &traders {
+        let buy = action_event_data.market_buy.abs() > 0.5;
+        let sell = action_event_data.market_sell.abs() > 0.5;
+        let Some(station) = trader.station.filter(|_| buy || sell) else {
+            continue;
+        };
+        let Some(item) = markets
+            .get(station)
+            .ok()
+            .and_then(|market| market.commodities.keys().nth(trader.selected))
+        else {
+            continue;
+        };
+
+        let (kind, quantity) = if buy {
+            (TradeKind::Buy, TRADE_BATCH)
+        } else {
+            (TradeKind::Sell, cargo_hold.quantity(item).min(TRADE_BATCH))
+        };
+        if quantity > 0 {
+            _ = trade_requests.send(TradeRequest {
+                ship,
+                station,
+                item: item.clone(),
+                quantity,
+                kind,
+            });
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn process_trades(
+    mut trade_requests: EventReader<TradeRequest>,
+    mut trades_completed: EventWriter<TradeCompleted>,
+    item_assets: Res<ItemAssets>,
+    catalogs: Res<Assets<ItemCatalog>>,
+    mut ships: Query<(&Transform, &mut Credits, &mut CargoHold)>,
+    mut markets: Query<(&Transform, &mut Market), Without<CargoHold>>,
+) {
+    let Some(catalog) = item_assets.catalog(&catalogs) else {
+        return;
+    };
+
+    for request in trade_requests.read() {
+        let (
+            Ok((ship_transform, mut credits, mut cargo_hold)),
+            Ok((station_transform, mut market)),
+        ) = (
+            ships.get_mut(request.ship),
+            markets.get_mut(request.station),
+        )
+        else {
+            continue;
+        };
+        if ship_transform
+            .translation
+            .distance(station_transform.translation)
+            > TRADE_RANGE
+        {
+            continue;
+        }
+
+        let result = match request.kind {
+            TradeKind::Buy => market.buy(
+                &request.item,
+                request.quantity,
+                &mut credits,
+                &mut cargo_hold,
+                catalog,
+            ),
+            TradeKind::Sell => market.sell(
+                &request.item,
+                request.quantity,
+                &mut credits,
+                &mut cargo_hold,
+                catalog,
+            ),
+        };
+
+        match result {
+            Ok(credits) => {
+                _ = trades_completed.send(TradeCompleted {
+                    request: request.clone(),
+                    credits,
+                });
+            }
+            Err(error) => warn!("Trade {request:?} failed: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::inventory_plugin::ItemDefinition;
+
+    fn ore() -> ItemId {
+        ItemId("iron_ore".to_owned())
+    }
+
+    fn catalog() -> ItemCatalog {
+        ItemCatalog {
+            items: BTreeMap::from([(
+                ore(),
+                ItemDefinition {
+                    name: "Iron Ore".to_owned(),
+                    unit_volume: 1.0,
+                    unit_mass: 1.0,
+                },
+            )]),
+        }
+    }
+
+    fn market(production: f32, consumption: f32) -> Market {
+        Market::new(&BTreeMap::from([(
+            ore(),
+            CommodityDefinition {
+                base_price: 100,
+                base_supply: 100.0,
+                base_demand: 100.0,
+                production,
+                consumption,
+                max_supply: 1_000.0,
+            },
+        )]))
+    }
+
+    #[test]
+    fn price_follows_supply_over_ticks() {
+        let mut surplus = market(10.0, 0.0);
+        let mut shortage = market(0.0, 10.0);
+        for _ in 0..5 {
+            surplus.tick();
+            shortage.tick();
+        }
+
+        let price = |market: &Market| {
+            market
+                .commodities
+                .get(&ore())
+                .map(CommodityMarket::buy_price)
+        };
+        assert!(price(&surplus) < Some(100));
+        assert!(price(&shortage) > Some(100));
+    }
+
+    #[test]
+    fn buying_and_selling_moves_credits_and_cargo() {
+        let catalog = catalog();
+        let mut market = market(0.0, 0.0);
+        let mut credits = Credits(1_000);
+        let mut cargo_hold = CargoHold::new(100.0, 100.0);
+
+        let cost = market.buy(&ore(), 5, &mut credits, &mut cargo_hold, &catalog);
+        assert_eq!(cost, Ok(500));
+        assert_eq!(credits, Credits(500));
+        assert_eq!(cargo_hold.quantity(&ore()), 5);
+
+        let payment = market.sell(&ore(), 5, &mut credits, &mut cargo_hold, &catalog);
+        assert!(payment.is_ok_and(|payment| payment < 500));
+        assert!(cargo_hold.is_empty());
+    }
+
+    #[test]
+    fn failed_trades_change_nothing() {
+        let catalog = catalog();
+        let mut market = market(0.0, 0.0);
+        let mut credits = Credits(50);
+        let mut cargo_hold = CargoHold::new(100.0, 100.0);
+
+        assert!(matches!(
+            market.buy(&ore(), 1, &mut credits, &mut cargo_hold, &catalog),
+            Err(TradeError::NotEnoughCredits { .. })
+        ));
+        assert!(matches!(
+            market.sell(&ore(), 1, &mut credits, &mut cargo_hold, &catalog),
+            Err(TradeError::Cargo(CargoError::NotEnoughItems { .. }))
+        ));
+        assert_eq!(credits, Credits(50));
+        assert!(cargo_hold.is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use derive_more::Display;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use bevy::prelude::*;
+
+use super::{
+    ship_plugin::{ActionEventData, PlayerId},
+    states_plugin::{reset_resource, FrameSystemsSet, MainState},
+    targeting_plugin::{ObjectScanned, Targetable},
+};
+
+#[derive(Debug)]
+pub struct ExplorationPlugin;
+
+impl Plugin for ExplorationPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .init_resource::<DiscoveryJournal>()
+            .add_event::<PointOfInterestDiscovered>()
+            .add_systems(
+                OnExit(MainState::InGame),
+                reset_resource::<DiscoveryJournal>,
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    reveal_discovered_points_of_interest
+                        .run_if(resource_changed::<DiscoveryJournal>),
+                    scan_for_points_of_interest,
+                )
+                    .chain()
+                    .in_set(FrameSystemsSet::Player),
+            );
+    }
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum PointOfInterestKind {
+    Derelict,
+    Anomaly,
+    Beacon,
+    Wreckage,
+}
+
+impl PointOfInterestKind {
+    pub const ALL: [Self; 4] = [Self::Derelict, Self::Anomaly, Self::Beacon, Self::Wreckage];
+}
+
+/// Something worth finding, hidden until a ship's scanner sweeps over it.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PointOfInterest {
+    pub kind: PointOfInterestKind,
+}
+
+/// Put on a point of interest once any ship has scanned it, making it visible and targetable.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Revealed;
+
+#[derive(Bundle)]
+pub struct PointOfInterestBundle {
+    point_of_interest: PointOfInterest,
+    name: Name,
+    spatial: SpatialBundle,
+    state_scoped: StateScoped<MainState>,
+}
+
+impl PointOfInterestBundle {
+    pub fn new(kind: PointOfInterestKind, name: String, transform: Transform) -> Self {
+        Self {
+            point_of_interest: PointOfInterest { kind },
+            name: Name::new(name),
+            spatial: SpatialBundle::from_transform(transform),
+            state_scoped: StateScoped(MainState::InGame),
+        }
+    }
+}
+
+/// Recharge state of a ship's scanner, put on every ship.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Scanner {
+    /// Seconds until the scanner can pulse again.
+    pub cooldown: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Discovery {
+    pub first_discovered_by: PlayerId,
+    pub discovered_by: BTreeSet<PlayerId>,
+}
+
+/// Every point of interest found so far, keyed by name, and who found it, kept in saves.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryJournal {
+    discoveries: BTreeMap<String, Discovery>,
+}
+
+impl DiscoveryJournal {
+    pub fn discoveries(&self) -> impl Iterator<Item = (&String, &Discovery)> {
+        self.discoveries.iter()
+    }
+
+    /// Everything `player` has found themselves, whether or not they were first.
+    pub fn discovered_by(&self, player: PlayerId) -> impl Iterator<Item = (&String, &Discovery)> {
+        self.discoveries
+            .iter()
+            .filter(move |(_, discovery)| discovery.discovered_by.contains(&player))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.discoveries.contains_key(name)
+    }
+
+    /// Records `player` finding `name`, returns whether they were the first to, or `None` if
+    /// they had already found it.
+    pub fn record(&mut self, name: &str, player: PlayerId) -> Option<bool> {
+        let discovery = self
+            .discoveries
+            .entry(name.to_owned())
+            .or_insert_with(|| Discovery {
+                first_discovered_by: player,
+                discovered_by: BTreeSet::new(),
+            });
+        discovery
+            .discovered_by
+            .insert(player)
+            .then_some(discovery.first_discovered_by == player)
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct PointOfInterestDiscovered {
+    pub ship: Entity,
+    pub player: PlayerId,
+    pub point_of_interest: Entity,
+    /// Whether nobody had found it before.
+    pub first: bool,
+}
+
+/// Reveals the points of interest a loaded journal says were already found.
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+#[expect(clippy::type_complexity, reason = "Bevy Query syntax")]
+fn reveal_discovered_points_of_interest(
+    mut commands: Commands,
+    journal: Res<DiscoveryJournal>,
+    points_of_interest: Query<(Entity, &Name), (With<PointOfInterest>, Without<Revealed>)>,
+) {
+    for (entity, name) in &points_of_interest {
+        if journal.contains(name) {
+            _ = commands.entity(entity).insert((Revealed, Targetable));
+        }
+    }
+}
+
+const SCANNER_RANGE: f32 = 800.0;
+const SCANNER_COOLDOWN: f32 = 3.0; // seconds
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn scan_for_points_of_interest(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut journal: ResMut<DiscoveryJournal>,
+    mut discovered: EventWriter<PointOfInterestDiscovered>,
+    mut object_scanned: EventWriter<ObjectScanned>,
+    mut ships: Query<(
+        Entity,
+        &PlayerId,
+        &Transform,
+        &ActionEventData,
+        &mut Scanner,
+    )>,
+    points_of_interest: Query<(Entity, &Name, &Transform, Has<Revealed>), With<PointOfInterest>>,
+) {
+    // Players scan in a fixed order so every peer agrees on who got there first
+    for (ship, &player, transform, action_event_data, mut scanner) in
+        ships.iter_mut().sorted_by_key(|(_, player, ..)| **player)
+    {
+        scanner.cooldown = (scanner.cooldown - time.delta_seconds()).max(0.0);
+        if action_event_data.scan.abs() < 0.5 || scanner.cooldown > 0.0 {
+            continue;
+        }
+        scanner.cooldown = SCANNER_COOLDOWN;
+
+        for (entity, name, poi_transform, revealed) in &points_of_interest {
+            if transform.translation.distance(poi_transform.translation) > SCANNER_RANGE {
+                continue;
+            }
+
+            if !revealed {
+                _ = commands.entity(entity).insert((Revealed, Targetable));
+            }
+            _ = object_scanned.send(ObjectScanned {
+                ship,
+                object: entity,
+            });
+
+            if let Some(first) = journal.record(name, player) {
+                _ = discovered.send(PointOfInterestDiscovered {
+                    ship,
+                    player,
+                    point_of_interest: entity,
+                    first,
+                });
+            }
+        }
+    }
+}
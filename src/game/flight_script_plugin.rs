@@ -0,0 +1,111 @@
+use core::error::Error;
+use std::{fs, io, path::Path};
+
+use derive_more::Display;
+use ron::error::SpannedError;
+use serde::{Deserialize, Serialize};
+
+use bevy::prelude::*;
+
+use crate::cli::CommandLineArguments;
+
+use super::{
+    ship_plugin::ActionEventData,
+    states_plugin::{FrameSystemsSet, GameModeState},
+};
+
+#[derive(Debug)]
+pub struct FlightScriptPlugin;
+
+impl Plugin for FlightScriptPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(Startup, load_from_command_line)
+            .add_systems(
+                FixedUpdate,
+                run_flight_scripts
+                    .in_set(FrameSystemsSet::Input)
+                    .run_if(in_state(GameModeState::Singleplayer)),
+            );
+    }
+}
+
+#[derive(Debug, Display)]
+pub enum FlightScriptError {
+    #[display("Could not access flight script: {_0}")]
+    Io(io::Error),
+    #[display("Could not read flight script: {_0}")]
+    Parse(SpannedError),
+}
+
+impl Error for FlightScriptError {}
+
+/// An input held for a while, the building block of a [`FlightScript`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ScriptSegment {
+    /// Seconds of simulation the input is held for.
+    pub duration: f32,
+    #[serde(default)]
+    pub input: ActionEventData,
+}
+
+/// Flies a ship through a list of timed inputs instead of a player, then lets go of the controls.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
+pub struct FlightScript {
+    pub segments: Vec<ScriptSegment>,
+    #[serde(skip)]
+    elapsed: f32,
+}
+
+impl FlightScript {
+    pub fn from_ron(ron: &str) -> Result<Self, FlightScriptError> {
+        ron::from_str(ron).map_err(FlightScriptError::Parse)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, FlightScriptError> {
+        Self::from_ron(&fs::read_to_string(path).map_err(FlightScriptError::Io)?)
+    }
+
+    /// The input of the segment running at `elapsed` seconds, none once the script has finished.
+    fn input_at(&self, elapsed: f32) -> Option<ActionEventData> {
+        let mut end = 0.0;
+        self.segments.iter().find_map(|segment| {
+            end += segment.duration;
+            (elapsed < end).then_some(segment.input)
+        })
+    }
+}
+
+/// The script the first player's ship is flown with, given on the command line.
+#[derive(Resource, Debug, Clone)]
+pub struct PlayerFlightScript(pub FlightScript);
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn load_from_command_line(mut commands: Commands, args: Option<Res<CommandLineArguments>>) {
+    let Some(path) = args.as_ref().and_then(|args| args.flight_script.as_ref()) else {
+        return;
+    };
+
+    match FlightScript::read(path) {
+        Ok(script) => {
+            info!("Flying the first ship with {}", path.display());
+            commands.insert_resource(PlayerFlightScript(script));
+        }
+        Err(error) => error!(
+            "Flying the first ship by hand, {} failed to load: {error}",
+            path.display()
+        ),
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn run_flight_scripts(
+    time: Res<Time>,
+    mut ships: Query<(&mut FlightScript, &mut ActionEventData)>,
+) {
+    for (mut script, mut action_event_data) in &mut ships {
+        *action_event_data = script.input_at(script.elapsed).unwrap_or_default();
+        script.elapsed += time.delta_seconds();
+    }
+}
@@ -0,0 +1,190 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    ship_plugin::{heading, ActionEventData, Controller},
+    states_plugin::FrameSystemsSet,
+    targeting_plugin::CurrentTarget,
+};
+
+#[derive(Debug)]
+pub struct FormationPlugin;
+
+impl Plugin for FormationPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(
+                FixedUpdate,
+                steer_formation_members.in_set(FrameSystemsSet::Input),
+            )
+            .add_systems(
+                FixedUpdate,
+                process_formation_commands.in_set(FrameSystemsSet::Player),
+            );
+    }
+}
+
+/// Standing order the leader has given to the wingmen of its formation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WingmanOrder {
+    #[default]
+    FormUp,
+    Break,
+    Attack,
+}
+
+/// Put on the leader ship, slots are offsets in the leader's local space.
+#[derive(Component, Debug, Clone)]
+pub struct Formation {
+    pub slots: Vec<Vec3>,
+    pub order: WingmanOrder,
+}
+
+impl Default for Formation {
+    /// A loose vee trailing behind the leader.
+    fn default() -> Self {
+        Self {
+            slots: vec![Vec3::new(-20.0, 0.0, -20.0), Vec3::new(20.0, 0.0, -20.0)],
+            order: WingmanOrder::default(),
+        }
+    }
+}
+
+/// Put on a follower ship, `slot` indexes into the leader's [`Formation::slots`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct FormationMember {
+    pub leader: Entity,
+    pub slot: usize,
+}
+
+fn process_formation_commands(mut query: Query<(&ActionEventData, &mut Formation)>) {
+    for (action_event_data, mut formation) in &mut query {
+        if action_event_data.form_up.abs() > 0.5 {
+            formation.order = WingmanOrder::FormUp;
+        } else if action_event_data.break_formation.abs() > 0.5 {
+            formation.order = WingmanOrder::Break;
+        } else if action_event_data.attack_my_target.abs() > 0.5 {
+            formation.order = WingmanOrder::Attack;
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn steer_formation_members(
+    mut members: Query<
+        (
+            &Transform,
+            &LinearVelocity,
+            &FormationMember,
+            &Controller,
+            &mut ActionEventData,
+        ),
+        Without<Formation>,
+    >,
+    leaders: Query<(&Transform, &LinearVelocity, &Formation, &CurrentTarget)>,
+    targets: Query<(&Transform, &LinearVelocity)>,
+) {
+    for (transform, velocity, member, controller, mut action_event_data) in &mut members {
+        // Replayed wingmen fly what they flew when recorded
+        if *controller != Controller::Ai {
+            continue;
+        }
+        let Ok((leader_transform, leader_velocity, formation, leader_target)) =
+            leaders.get(member.leader)
+        else {
+            *action_event_data = ActionEventData::default();
+            continue;
+        };
+
+        // Wingmen engage whatever the leader has selected, lock or not
+        let attack_target = leader_target
+            .entity
+            .filter(|_| formation.order == WingmanOrder::Attack)
+            .and_then(|target| targets.get(target).ok());
+
+        *action_event_data = match (formation.order, attack_target) {
+            (WingmanOrder::Attack, Some((target_transform, target_velocity))) => steer_towards(
+                transform,
+                velocity.0,
+                target_transform.translation,
+                target_velocity.0,
+            ),
+            // Without a target to attack, wingmen stay in formation
+            (WingmanOrder::FormUp | WingmanOrder::Attack, _) => formation
+                .slots
+                .get(member.slot)
+                .map_or_else(ActionEventData::default, |&offset| {
+                    hold_slot(
+                        transform,
+                        velocity.0,
+                        leader_transform,
+                        leader_velocity.0,
+                        offset,
+                    )
+                }),
+            // Released from formation, let auto balance bring the ship to rest
+            (WingmanOrder::Break, _) => ActionEventData::default(),
+        };
+    }
+}
+
+fn hold_slot(
+    transform: &Transform,
+    velocity: Vec3,
+    leader_transform: &Transform,
+    leader_velocity: Vec3,
+    offset: Vec3,
+) -> ActionEventData {
+    let slot_position = leader_transform.transform_point(offset);
+    let approach = steer_towards(transform, velocity, slot_position, leader_velocity);
+    if transform.translation.distance(slot_position) < SLOT_TOLERANCE {
+        // Settled in the slot, look where the leader looks while keeping station
+        let look_ahead = transform.translation + *heading(leader_transform) * STEERING_MAX_SPEED;
+        ActionEventData {
+            thrust: approach.thrust,
+            ..steer_towards(transform, velocity, look_ahead, velocity)
+        }
+    } else {
+        approach
+    }
+}
+
+const SLOT_TOLERANCE: f32 = 5.0;
+
+const STEERING_TURN_GAIN: f32 = 2.0;
+const STEERING_APPROACH_RATE: f32 = 0.5;
+const STEERING_MAX_SPEED: f32 = 50.0;
+const STEERING_THRUST_GAIN: f32 = 0.2;
+
+/// Produces the inputs that turn the ship's nose towards `target` and close in on it,
+/// arriving with `target_velocity`.
+pub fn steer_towards(
+    transform: &Transform,
+    velocity: Vec3,
+    target: Vec3,
+    target_velocity: Vec3,
+) -> ActionEventData {
+    let offset = target - transform.translation;
+    let local_direction = (transform.rotation.inverse() * offset).normalize_or_zero();
+
+    // Positive pitch dips the nose and positive yaw swings it left, see `process_actions`
+    let pitch = (-local_direction.y * STEERING_TURN_GAIN).clamp(-1.0, 1.0);
+    let yaw = if local_direction.z < 0.0 {
+        // Target is behind us, turn hard
+        -local_direction.x.signum()
+    } else {
+        (-local_direction.x * STEERING_TURN_GAIN).clamp(-1.0, 1.0)
+    };
+
+    let desired_velocity =
+        target_velocity + (offset * STEERING_APPROACH_RATE).clamp_length_max(STEERING_MAX_SPEED);
+    let thrust = ((desired_velocity - velocity).dot(*heading(transform)) * STEERING_THRUST_GAIN)
+        .clamp(-1.0, 1.0);
+
+    ActionEventData {
+        thrust,
+        pitch,
+        yaw,
+        ..default()
+    }
+}
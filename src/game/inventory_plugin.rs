@@ -0,0 +1,323 @@
+use core::{error::Error, mem};
+use std::collections::BTreeMap;
+
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use avian3d::prelude::*;
+
+use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+
+use super::{
+    loading_plugin::LoadingAppExt,
+    ship_plugin::{heading, ActionEventData},
+    states_plugin::{FrameSystemsSet, MainState},
+    targeting_plugin::Targetable,
+};
+
+#[derive(Debug)]
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_plugins(RonAssetPlugin::<ItemCatalog>::new(&["items.ron"]))
+            .load_tracked_collection::<ItemAssets>()
+            .add_systems(
+                FixedUpdate,
+                (jettison_cargo, pick_up_cargo_containers).in_set(FrameSystemsSet::Player),
+            )
+            .add_systems(
+                FixedUpdate,
+                apply_cargo_mass.in_set(FrameSystemsSet::Physics),
+            );
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct ItemAssets {
+    #[asset(path = "items/catalog.items.ron")]
+    pub catalog: Handle<ItemCatalog>,
+}
+
+impl ItemAssets {
+    pub fn catalog<'a>(&self, catalogs: &'a Assets<ItemCatalog>) -> Option<&'a ItemCatalog> {
+        catalogs.get(&self.catalog)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Display, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct ItemId(pub String);
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemDefinition {
+    pub name: String,
+    /// Cubic metres taken up by a single unit.
+    pub unit_volume: f32,
+    /// Tonnes added to the ship by a single unit.
+    pub unit_mass: f32,
+}
+
+/// Every item known to the game, loaded from `assets/items/*.items.ron`.
+#[derive(Asset, TypePath, Deserialize, Clone, Debug)]
+pub struct ItemCatalog {
+    pub items: BTreeMap<ItemId, ItemDefinition>,
+}
+
+impl ItemCatalog {
+    pub fn get(&self, item: &ItemId) -> Result<&ItemDefinition, CargoError> {
+        self.items
+            .get(item)
+            .ok_or_else(|| CargoError::UnknownItem(item.clone()))
+    }
+}
+
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum CargoError {
+    #[display("Unknown item {_0}")]
+    UnknownItem(ItemId),
+    #[display("Not enough room in the hold for {quantity} {item}")]
+    NotEnoughRoom { item: ItemId, quantity: u32 },
+    #[display("Not enough {item} in the hold to remove {quantity}")]
+    NotEnoughItems { item: ItemId, quantity: u32 },
+}
+
+impl Error for CargoError {}
+
+/// Items carried by a ship, bounded by both volume and mass.
+#[derive(Component, Debug, Clone, Default)]
+pub struct CargoHold {
+    pub volume_capacity: f32,
+    pub mass_capacity: f32,
+    items: BTreeMap<ItemId, u32>,
+    volume: f32,
+    mass: f32,
+    /// Cargo mass last added onto the ship's rigid body [`Mass`].
+    applied_mass: f32,
+}
+
+impl CargoHold {
+    pub fn new(volume_capacity: f32, mass_capacity: f32) -> Self {
+        Self {
+            volume_capacity,
+            mass_capacity,
+            ..default()
+        }
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = (&ItemId, u32)> {
+        self.items.iter().map(|(item, &quantity)| (item, quantity))
+    }
+
+    pub fn quantity(&self, item: &ItemId) -> u32 {
+        self.items.get(item).copied().unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub const fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    pub const fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    /// How many more units of `definition` fit, limited by whichever of volume or mass runs out first.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "Room is clamped to be non negative and floored"
+    )]
+    pub fn room_for(&self, definition: &ItemDefinition) -> u32 {
+        let by_volume = (self.volume_capacity - self.volume) / definition.unit_volume;
+        let by_mass = (self.mass_capacity - self.mass) / definition.unit_mass;
+        by_volume.min(by_mass).max(0.0).floor() as u32
+    }
+
+    pub fn add(
+        &mut self,
+        item: &ItemId,
+        quantity: u32,
+        catalog: &ItemCatalog,
+    ) -> Result<(), CargoError> {
+        let definition = catalog.get(item)?;
+        if self.room_for(definition) < quantity {
+            return Err(CargoError::NotEnoughRoom {
+                item: item.clone(),
+                quantity,
+            });
+        }
+
+        *self.items.entry(item.clone()).or_default() += quantity;
+        self.update_totals(definition, quantity, 1.0);
+        Ok(())
+    }
+
+    pub fn remove(
+        &mut self,
+        item: &ItemId,
+        quantity: u32,
+        catalog: &ItemCatalog,
+    ) -> Result<(), CargoError> {
+        let definition = catalog.get(item)?;
+        let Some(stored) = self
+            .items
+            .get_mut(item)
+            .filter(|stored| **stored >= quantity)
+        else {
+            return Err(CargoError::NotEnoughItems {
+                item: item.clone(),
+                quantity,
+            });
+        };
+
+        *stored -= quantity;
+        if *stored == 0 {
+            _ = self.items.remove(item);
+        }
+        self.update_totals(definition, quantity, -1.0);
+        Ok(())
+    }
+
+    /// Empties the hold, handing back everything that was in it.
+    pub fn take_all(&mut self) -> BTreeMap<ItemId, u32> {
+        self.volume = 0.0;
+        self.mass = 0.0;
+        mem::take(&mut self.items)
+    }
+
+    #[expect(clippy::cast_precision_loss, reason = "Cargo quantities are small")]
+    fn update_totals(&mut self, definition: &ItemDefinition, quantity: u32, sign: f32) {
+        self.volume += sign * definition.unit_volume * quantity as f32;
+        self.mass += sign * definition.unit_mass * quantity as f32;
+    }
+}
+
+/// Loose cargo floating in space, ships pick it up by flying into it.
+#[derive(Component, Debug, Clone, Default)]
+pub struct CargoContainer {
+    pub items: BTreeMap<ItemId, u32>,
+}
+
+#[derive(Bundle)]
+pub struct CargoContainerBundle {
+    container: CargoContainer,
+    spatial: SpatialBundle,
+    state_scoped: StateScoped<MainState>,
+    rigid_body: RigidBody,
+    collider: Collider,
+    linear_velocity: LinearVelocity,
+    targetable: Targetable,
+}
+
+impl CargoContainerBundle {
+    pub fn new(items: BTreeMap<ItemId, u32>, transform: Transform, velocity: Vec3) -> Self {
+        Self {
+            container: CargoContainer { items },
+            spatial: SpatialBundle::from_transform(transform),
+            state_scoped: StateScoped(MainState::InGame),
+            rigid_body: RigidBody::Dynamic,
+            collider: Collider::cuboid(
+                CARGO_CONTAINER_SIZE,
+                CARGO_CONTAINER_SIZE,
+                CARGO_CONTAINER_SIZE,
+            ),
+            linear_velocity: LinearVelocity(velocity),
+            targetable: Targetable,
+        }
+    }
+}
+
+pub const CARGO_CONTAINER_SIZE: f32 = 2.0;
+
+/// How far behind the ship jettisoned cargo appears, clear of the ship's own collider.
+const JETTISON_DISTANCE: f32 = 15.0;
+const JETTISON_SPEED: f32 = 5.0;
+
+fn jettison_cargo(
+    mut commands: Commands,
+    mut query: Query<(
+        &Transform,
+        &LinearVelocity,
+        &ActionEventData,
+        &mut CargoHold,
+    )>,
+) {
+    for (transform, velocity, action_event_data, mut cargo_hold) in &mut query {
+        if action_event_data.jettison.abs() < 0.5 || cargo_hold.is_empty() {
+            continue;
+        }
+
+        let backwards = -*heading(transform);
+        let container_transform =
+            Transform::from_translation(transform.translation + backwards * JETTISON_DISTANCE);
+        _ = commands.spawn(CargoContainerBundle::new(
+            cargo_hold.take_all(),
+            container_transform,
+            velocity.0 + backwards * JETTISON_SPEED,
+        ));
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn pick_up_cargo_containers(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    item_assets: Res<ItemAssets>,
+    catalogs: Res<Assets<ItemCatalog>>,
+    mut holds: Query<&mut CargoHold>,
+    mut containers: Query<&mut CargoContainer>,
+) {
+    let Some(catalog) = item_assets.catalog(&catalogs) else {
+        return;
+    };
+
+    for &CollisionStarted(a, b) in collisions.read() {
+        let (ship, container) = if holds.contains(a) { (a, b) } else { (b, a) };
+        let (Ok(mut cargo_hold), Ok(mut container_items)) =
+            (holds.get_mut(ship), containers.get_mut(container))
+        else {
+            continue;
+        };
+
+        // Take whatever fits, anything left stays floating in the container
+        for (item, quantity) in &mut container_items.items {
+            let Ok(definition) = catalog.get(item) else {
+                continue;
+            };
+            let taken = cargo_hold.room_for(definition).min(*quantity);
+            if taken > 0 && cargo_hold.add(item, taken, catalog).is_ok() {
+                *quantity -= taken;
+            }
+        }
+        container_items.items.retain(|_, quantity| *quantity > 0);
+
+        if container_items.items.is_empty() {
+            commands.entity(container).despawn_recursive();
+        }
+    }
+}
+
+/// Adds the cargo on top of the ship's own mass, scaling inertia along with it so a loaded ship
+/// turns as sluggishly as it accelerates.
+fn apply_cargo_mass(
+    mut query: Query<(&mut CargoHold, &mut Mass, &mut Inertia), Changed<CargoHold>>,
+) {
+    for (mut cargo_hold, mut mass, mut inertia) in &mut query {
+        let delta = cargo_hold.mass - cargo_hold.applied_mass;
+        if delta.abs() < f32::EPSILON || mass.0 <= f32::EPSILON {
+            continue;
+        }
+
+        let new_mass = mass.0 + delta;
+        inertia.0 *= new_mass / mass.0;
+        mass.0 = new_mass;
+        cargo_hold.bypass_change_detection().applied_mass = cargo_hold.mass;
+    }
+}
@@ -0,0 +1,190 @@
+use core::{marker::PhantomData, time::Duration};
+
+use bevy::{
+    asset::{LoadState, RecursiveDependencyLoadState, UntypedAssetId},
+    prelude::*,
+};
+use bevy_asset_loader::prelude::*;
+
+use super::states_plugin::MainState;
+
+#[derive(Debug)]
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .init_resource::<LoadingProgress>()
+            .configure_sets(
+                Update,
+                PrepareLoadedAssetsSet
+                    .after(LoadingStateSet(MainState::Loading))
+                    .run_if(in_state(MainState::Loading)),
+            )
+            .add_systems(OnEnter(MainState::Loading), start_loading)
+            .add_systems(
+                Update,
+                (update_loading_progress, finish_loading)
+                    .chain()
+                    .after(PrepareLoadedAssetsSet)
+                    .run_if(in_state(MainState::Loading)),
+            );
+    }
+}
+
+/// Systems turning freshly loaded collections into what the game needs, loading finishes after them.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrepareLoadedAssetsSet;
+
+/// Keeps the loading screen up at least this long so a fast load doesn't flash it.
+const MIN_LOADING_DURATION: Duration = Duration::from_millis(750);
+
+/// How far along loading every registered asset collection is.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LoadingProgress {
+    pub done: usize,
+    pub total: usize,
+    /// Assets that failed to load, with the reason, loading can't finish while there are any.
+    pub failures: Vec<String>,
+    /// Failures from preparing loaded assets for the game, which the asset server doesn't know about.
+    reported: Vec<String>,
+    collections: Vec<TrackedCollection>,
+    started: Duration,
+}
+
+impl LoadingProgress {
+    /// Fraction of assets loaded, in `[0, 1]`.
+    #[expect(clippy::cast_precision_loss, reason = "Asset counts are small")]
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.done as f32 / self.total as f32
+        }
+    }
+
+    /// Stops loading from finishing, e.g. when a loaded asset turns out to be unusable.
+    pub fn report_failure(&mut self, failure: String) {
+        self.reported.push(failure);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.failures.is_empty() && self.collections.iter().all(|collection| collection.created)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TrackedCollection {
+    handles: Vec<UntypedHandle>,
+    /// Whether the collection resource has been put together from its loaded handles.
+    created: bool,
+}
+
+pub trait LoadingAppExt {
+    /// Loads the collection while in [`MainState::Loading`], counting it towards [`LoadingProgress`].
+    fn load_tracked_collection<T: AssetCollection>(&mut self) -> &mut Self;
+}
+
+impl LoadingAppExt for App {
+    fn load_tracked_collection<T: AssetCollection>(&mut self) -> &mut Self {
+        self.configure_loading_state(
+            LoadingStateConfig::new(MainState::Loading).load_collection::<T>(),
+        )
+        .add_systems(
+            OnEnter(MainState::Loading),
+            track_collection::<T>.after(start_loading),
+        )
+        .add_systems(
+            Update,
+            mark_collection_created::<T>
+                .run_if(resource_exists::<T>)
+                .in_set(PrepareLoadedAssetsSet),
+        )
+    }
+}
+
+/// Index of a collection in [`LoadingProgress`], one per tracked collection type.
+#[derive(Resource)]
+struct TrackedCollectionIndex<T> {
+    index: usize,
+    marker: PhantomData<T>,
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn start_loading(time: Res<Time>, mut progress: ResMut<LoadingProgress>) {
+    *progress = LoadingProgress {
+        started: time.elapsed(),
+        ..default()
+    };
+}
+
+fn track_collection<T: AssetCollection>(world: &mut World) {
+    // Asks for the same assets the loading state does, the asset server hands back the same handles
+    let handles = T::load(world);
+    let mut progress = world.resource_mut::<LoadingProgress>();
+    let index = progress.collections.len();
+    progress.collections.push(TrackedCollection {
+        handles,
+        created: false,
+    });
+    world.insert_resource(TrackedCollectionIndex::<T> {
+        index,
+        marker: PhantomData,
+    });
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn mark_collection_created<T: AssetCollection>(
+    index: Res<TrackedCollectionIndex<T>>,
+    mut progress: ResMut<LoadingProgress>,
+) {
+    if let Some(collection) = progress.collections.get_mut(index.index) {
+        collection.created = true;
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_loading_progress(asset_server: Res<AssetServer>, mut progress: ResMut<LoadingProgress>) {
+    let handles = progress
+        .collections
+        .iter()
+        .flat_map(|collection| &collection.handles)
+        .map(UntypedHandle::id)
+        .collect::<Vec<_>>();
+
+    progress.total = handles.len();
+    progress.done = handles
+        .iter()
+        .filter(|&&id| asset_server.is_loaded_with_dependencies(id))
+        .count();
+    progress.failures = handles
+        .iter()
+        .filter_map(|&id| load_failure(&asset_server, id))
+        .chain(progress.reported.iter().cloned())
+        .collect();
+}
+
+fn load_failure(asset_server: &AssetServer, id: UntypedAssetId) -> Option<String> {
+    let path = asset_server
+        .get_path(id)
+        .map_or_else(|| format!("{id:?}"), |path| path.to_string());
+    match asset_server.get_load_state(id) {
+        Some(LoadState::Failed(error)) => Some(format!("{path}: {error}")),
+        _ => matches!(
+            asset_server.get_recursive_dependency_load_state(id),
+            Some(RecursiveDependencyLoadState::Failed)
+        )
+        .then(|| format!("{path}: a dependency failed to load")),
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn finish_loading(
+    time: Res<Time>,
+    progress: Res<LoadingProgress>,
+    mut next_main_state: ResMut<NextState<MainState>>,
+) {
+    if progress.is_finished() && time.elapsed() >= progress.started + MIN_LOADING_DURATION {
+        next_main_state.set(MainState::InGame);
+    }
+}
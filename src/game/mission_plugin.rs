@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+
+use derive_more::Display;
+use serde::Deserialize;
+
+use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+
+use super::{
+    economy_plugin::{Credits, Station, TradeCompleted, TradeKind},
+    formation_plugin::FormationMember,
+    inventory_plugin::{CargoHold, ItemAssets, ItemCatalog, ItemId},
+    loading_plugin::LoadingAppExt,
+    ship_plugin::Ship,
+    states_plugin::{reset_resource, FrameSystemsSet, MainState},
+    targeting_plugin::{Destroyed, ObjectScanned},
+};
+
+#[derive(Debug)]
+pub struct MissionPlugin;
+
+impl Plugin for MissionPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_plugins(RonAssetPlugin::<MissionCatalog>::new(&["missions.ron"]))
+            .load_tracked_collection::<MissionAssets>()
+            .init_resource::<MissionLog>()
+            .add_event::<MissionAccepted>()
+            .add_event::<ObjectiveCompleted>()
+            .add_event::<MissionCompleted>()
+            .add_systems(OnExit(MainState::InGame), reset_resource::<MissionLog>)
+            .add_systems(
+                FixedUpdate,
+                (
+                    accept_starting_missions,
+                    track_objectives,
+                    complete_missions,
+                )
+                    .chain()
+                    .in_set(FrameSystemsSet::Progression),
+            );
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct MissionAssets {
+    #[asset(path = "missions/catalog.missions.ron")]
+    pub catalog: Handle<MissionCatalog>,
+}
+
+impl MissionAssets {
+    pub fn catalog<'a>(&self, catalogs: &'a Assets<MissionCatalog>) -> Option<&'a MissionCatalog> {
+        catalogs.get(&self.catalog)
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Display, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct MissionId(pub String);
+
+#[derive(Deserialize, Clone, Debug, Display)]
+pub enum ObjectiveDefinition {
+    #[display("Fly to ({}, {}, {})", position[0], position[1], position[2])]
+    ReachLocation { position: [f32; 3], radius: f32 },
+    #[display("Sell {quantity} {item} at {station}")]
+    DeliverCargo {
+        station: String,
+        item: ItemId,
+        quantity: u32,
+    },
+    #[display("Destroy {count} {name}")]
+    DestroyTarget { name: String, count: u32 },
+    #[display("Scan {name}")]
+    ScanObject { name: String },
+}
+
+impl ObjectiveDefinition {
+    /// How much progress completes the objective.
+    pub const fn required(&self) -> u32 {
+        match self {
+            Self::DeliverCargo { quantity, .. } => *quantity,
+            Self::DestroyTarget { count, .. } => *count,
+            Self::ReachLocation { .. } | Self::ScanObject { .. } => 1,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Reward {
+    #[serde(default)]
+    pub credits: u64,
+    #[serde(default)]
+    pub items: BTreeMap<ItemId, u32>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MissionDefinition {
+    pub title: String,
+    pub objectives: Vec<ObjectiveDefinition>,
+    #[serde(default)]
+    pub reward: Reward,
+    /// Given to every player as soon as they spawn.
+    #[serde(default)]
+    pub starting: bool,
+    /// Follow up mission offered once this one completes.
+    #[serde(default)]
+    pub next: Option<MissionId>,
+}
+
+/// Every mission in the game, loaded from `assets/missions/*.missions.ron`.
+#[derive(Asset, TypePath, Deserialize, Clone, Debug)]
+pub struct MissionCatalog {
+    pub missions: BTreeMap<MissionId, MissionDefinition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ActiveMission {
+    pub mission: MissionId,
+    pub ship: Entity,
+    /// Progress towards each objective, indexed like [`MissionDefinition::objectives`].
+    pub progress: Vec<u32>,
+}
+
+/// Missions every player is working on or has finished.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MissionLog {
+    pub active: Vec<ActiveMission>,
+    pub completed: Vec<(Entity, MissionId)>,
+}
+
+impl MissionLog {
+    fn accept(&mut self, ship: Entity, mission: &MissionId, definition: &MissionDefinition) {
+        self.active.push(ActiveMission {
+            mission: mission.clone(),
+            ship,
+            progress: vec![0; definition.objectives.len()],
+        });
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct MissionAccepted {
+    pub ship: Entity,
+    pub mission: MissionId,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct ObjectiveCompleted {
+    pub ship: Entity,
+    pub mission: MissionId,
+    pub objective: usize,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct MissionCompleted {
+    pub ship: Entity,
+    pub mission: MissionId,
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn accept_starting_missions(
+    mission_assets: Res<MissionAssets>,
+    catalogs: Res<Assets<MissionCatalog>>,
+    mut mission_log: ResMut<MissionLog>,
+    mut missions_accepted: EventWriter<MissionAccepted>,
+    // Wingmen follow their leader rather than take on missions of their own
+    ships: Query<Entity, (Added<Ship>, Without<FormationMember>)>,
+) {
+    let Some(catalog) = mission_assets.catalog(&catalogs) else {
+        return;
+    };
+
+    for ship in &ships {
+        for (mission, definition) in &catalog.missions {
+            if definition.starting {
+                mission_log.accept(ship, mission, definition);
+                _ = missions_accepted.send(MissionAccepted {
+                    ship,
+                    mission: mission.clone(),
+                });
+            }
+        }
+    }
+}
+
+#[expect(
+    clippy::too_many_arguments,
+    clippy::needless_pass_by_value,
+    reason = "Bevy System syntax"
+)]
+fn track_objectives(
+    mission_assets: Res<MissionAssets>,
+    catalogs: Res<Assets<MissionCatalog>>,
+    mut mission_log: ResMut<MissionLog>,
+    mut trades_completed: EventReader<TradeCompleted>,
+    mut destroyed: EventReader<Destroyed>,
+    mut objects_scanned: EventReader<ObjectScanned>,
+    mut objectives_completed: EventWriter<ObjectiveCompleted>,
+    ships: Query<&Transform, With<Ship>>,
+    stations: Query<&Station>,
+    names: Query<&Name>,
+) {
+    let Some(catalog) = mission_assets.catalog(&catalogs) else {
+        return;
+    };
+
+    let trades = trades_completed.read().collect::<Vec<_>>();
+    let destroyed = destroyed.read().collect::<Vec<_>>();
+    let scanned = objects_scanned.read().collect::<Vec<_>>();
+
+    for active in &mut mission_log.active {
+        let Some(definition) = catalog.missions.get(&active.mission) else {
+            continue;
+        };
+
+        for (objective_index, (objective, progress)) in definition
+            .objectives
+            .iter()
+            .zip(active.progress.iter_mut())
+            .enumerate()
+        {
+            let required = objective.required();
+            if *progress >= required {
+                continue;
+            }
+
+            let ship = active.ship;
+            let gained = match objective {
+                ObjectiveDefinition::ReachLocation {
+                    position: [x, y, z],
+                    radius,
+                } => ships.get(ship).map_or(0, |transform| {
+                    u32::from(transform.translation.distance(Vec3::new(*x, *y, *z)) <= *radius)
+                }),
+                ObjectiveDefinition::DeliverCargo {
+                    station,
+                    item,
+                    quantity: _,
+                } => trades
+                    .iter()
+                    .filter(|trade| {
+                        let request = &trade.request;
+                        request.ship == ship
+                            && request.kind == TradeKind::Sell
+                            && request.item == *item
+                            && stations
+                                .get(request.station)
+                                .is_ok_and(|trade_station| trade_station.name == *station)
+                    })
+                    .map(|trade| trade.request.quantity)
+                    .sum(),
+                ObjectiveDefinition::DestroyTarget { name, count: _ } => {
+                    let count = destroyed
+                        .iter()
+                        .filter(|event| {
+                            event.attacker == Some(ship)
+                                && event
+                                    .name
+                                    .as_ref()
+                                    .is_some_and(|destroyed| destroyed == name)
+                        })
+                        .count();
+                    u32::try_from(count).unwrap_or(u32::MAX)
+                }
+                ObjectiveDefinition::ScanObject { name } => {
+                    u32::from(scanned.iter().any(|event| {
+                        event.ship == ship
+                            && names
+                                .get(event.object)
+                                .is_ok_and(|scanned| scanned.as_str() == name)
+                    }))
+                }
+            };
+
+            if gained > 0 {
+                *progress = progress.saturating_add(gained).min(required);
+                if *progress >= required {
+                    _ = objectives_completed.send(ObjectiveCompleted {
+                        ship,
+                        mission: active.mission.clone(),
+                        objective: objective_index,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[expect(
+    clippy::too_many_arguments,
+    clippy::needless_pass_by_value,
+    reason = "Bevy System syntax"
+)]
+fn complete_missions(
+    mission_assets: Res<MissionAssets>,
+    catalogs: Res<Assets<MissionCatalog>>,
+    item_assets: Res<ItemAssets>,
+    item_catalogs: Res<Assets<ItemCatalog>>,
+    mut mission_log: ResMut<MissionLog>,
+    mut missions_accepted: EventWriter<MissionAccepted>,
+    mut missions_completed: EventWriter<MissionCompleted>,
+    mut ships: Query<(&mut Credits, &mut CargoHold)>,
+) {
+    let (Some(catalog), Some(item_catalog)) = (
+        mission_assets.catalog(&catalogs),
+        item_assets.catalog(&item_catalogs),
+    ) else {
+        return;
+    };
+
+    let (finished, active): (Vec<_>, Vec<_>) = mission_log.active.drain(..).partition(|active| {
+        catalog
+            .missions
+            .get(&active.mission)
+            .is_some_and(|definition| {
+                definition
+                    .objectives
+                    .iter()
+                    .zip(&active.progress)
+                    .all(|(objective, &progress)| progress >= objective.required())
+            })
+    });
+    mission_log.active = active;
+
+    for ActiveMission { mission, ship, .. } in finished {
+        let Some(definition) = catalog.missions.get(&mission) else {
+            continue;
+        };
+
+        if let Ok((mut credits, mut cargo_hold)) = ships.get_mut(ship) {
+            credits.0 += definition.reward.credits;
+            for (item, &quantity) in &definition.reward.items {
+                if let Err(error) = cargo_hold.add(item, quantity, item_catalog) {
+                    warn!("Mission reward for {mission} lost: {error}");
+                }
+            }
+        }
+
+        if let Some((next, next_definition)) = definition
+            .next
+            .as_ref()
+            .and_then(|next| catalog.missions.get_key_value(next))
+        {
+            mission_log.accept(ship, next, next_definition);
+            _ = missions_accepted.send(MissionAccepted {
+                ship,
+                mission: next.clone(),
+            });
+        }
+
+        mission_log.completed.push((ship, mission.clone()));
+        _ = missions_completed.send(MissionCompleted { ship, mission });
+    }
+}
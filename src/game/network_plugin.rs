@@ -1,14 +1,141 @@
-use bevy::app::{App, Plugin};
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::cli::CommandLineArguments;
+
+use super::{
+    ship_plugin::{ActionEventData, Controller, PlayerId},
+    states_plugin::{reset_resource, FrameSystemsSet, GameModeState},
+};
 
 #[derive(Debug)]
 pub struct NetworkingPlugin;
 
 impl Plugin for NetworkingPlugin {
     fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(OnEnter(GameModeState::Multiplayer), start_session)
+            .init_resource::<RemoteInputs>()
+            .add_systems(
+                OnExit(GameModeState::Multiplayer),
+                (end_session, reset_resource::<RemoteInputs>),
+            )
+            .add_systems(
+                FixedUpdate,
+                advance_session
+                    .in_set(FrameSystemsSet::World)
+                    .run_if(in_state(GameModeState::Multiplayer)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (loop_back_local_inputs.run_if(synctest), apply_remote_inputs)
+                    .chain()
+                    .in_set(FrameSystemsSet::Input)
+                    .run_if(in_state(GameModeState::Multiplayer)),
+            );
+
         enable_debug(app);
     }
 }
 
+/// The p2p session joined in multiplayer, absent in single-player.
+#[derive(Resource, Debug, Clone)]
+pub struct NetworkSession {
+    pub session_id: String,
+    pub player_count: u8,
+    pub synctest: bool,
+    /// Simulation frames advanced together with the other peers.
+    pub frame: u64,
+}
+
+/// The latest inputs of the players on other peers, which their ships keep flying with until
+/// newer ones arrive.
+#[derive(Resource, Debug, Default)]
+pub struct RemoteInputs {
+    inputs: HashMap<PlayerId, ActionEventData>,
+}
+
+impl RemoteInputs {
+    pub fn receive(&mut self, player: PlayerId, input: ActionEventData) {
+        _ = self.inputs.insert(player, input);
+    }
+}
+
+fn start_session(mut commands: Commands, args: Option<Res<CommandLineArguments>>) {
+    let session = args.map_or_else(
+        || NetworkSession {
+            session_id: "spacerama".to_owned(),
+            player_count: 1,
+            synctest: false,
+            frame: 0,
+        },
+        |args| NetworkSession {
+            session_id: args.session_id.clone(),
+            player_count: args.player_count,
+            synctest: args.synctest,
+            frame: 0,
+        },
+    );
+    info!(
+        "Starting session {} for {} players (synctest: {})",
+        session.session_id, session.player_count, session.synctest
+    );
+    commands.insert_resource(session);
+}
+
+fn end_session(mut commands: Commands) {
+    commands.remove_resource::<NetworkSession>();
+}
+
+fn advance_session(mut session: ResMut<NetworkSession>) {
+    session.frame += 1;
+}
+
+fn synctest(session: Option<Res<NetworkSession>>) -> bool {
+    session.is_some_and(|session| session.synctest)
+}
+
+/// Without peers to play against, synctest feeds the first local player's inputs to every remote
+/// ship so the remote path still gets exercised.
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn loop_back_local_inputs(
+    ships: Query<(&PlayerId, &Controller, &ActionEventData)>,
+    mut remote_inputs: ResMut<RemoteInputs>,
+) {
+    let Some(&input) = ships
+        .iter()
+        .filter(|(_, controller, _)| **controller == Controller::LocalPlayer)
+        .min_by_key(|(player, ..)| **player)
+        .map(|(.., input)| input)
+    else {
+        return;
+    };
+
+    for (&player, &controller, _) in &ships {
+        if controller == Controller::Remote {
+            remote_inputs.receive(player, input);
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn apply_remote_inputs(
+    remote_inputs: Res<RemoteInputs>,
+    mut ships: Query<(&PlayerId, &Controller, &mut ActionEventData)>,
+) {
+    for (player, &controller, mut action_event_data) in &mut ships {
+        if controller != Controller::Remote {
+            continue;
+        }
+        if let Some(&input) = remote_inputs.inputs.get(player) {
+            *action_event_data = input;
+        }
+    }
+}
+
+#[expect(
+    clippy::missing_const_for_fn,
+    reason = "Debug plugins will be added here"
+)]
 fn enable_debug(_app: &mut App) {
     #[cfg(debug_assertions)]
     {
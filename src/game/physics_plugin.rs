@@ -3,6 +3,9 @@ use avian3d::prelude::*;
 use bevy::app::FixedUpdate;
 use bevy::app::{App, Plugin};
 use bevy::math::Vec3;
+use bevy::prelude::*;
+
+use super::states_plugin::{GameModeState, InGameState};
 
 #[derive(Debug)]
 pub struct PhysicsPlugin;
@@ -11,13 +14,47 @@ impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
         _ = app
             .add_plugins(PhysicsPlugins::new(FixedUpdate))
-            .insert_resource(Gravity(Vec3::ZERO)); // Disable Gravity since we are doing outer space experience for now
+            .insert_resource(Gravity(Vec3::ZERO)) // Disable Gravity since we are doing outer space experience for now
+            .add_systems(FixedFirst, step_once_per_tick)
+            // Other players keep flying while the pause menu is open in multiplayer
+            .add_systems(
+                OnEnter(InGameState::Paused),
+                pause_physics.run_if(in_state(GameModeState::Singleplayer)),
+            )
+            .add_systems(OnExit(InGameState::Paused), resume_physics);
 
         enable_debug(app);
     }
 }
 
+/// Steps physics once per tick by the tick's length, accumulating time on its own clock skips
+/// steps and the thrust given for them whenever its rate drifts from [`FixedUpdate`]'s.
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn step_once_per_tick(fixed: Res<Time<Fixed>>, mut physics: ResMut<Time<Physics>>) {
+    let timestep = TimestepMode::FixedOnce {
+        delta: fixed.timestep(),
+    };
+    if physics.timestep_mode() != timestep {
+        *physics.timestep_mode_mut() = timestep;
+    }
+}
+
+fn pause_physics(mut time: ResMut<Time<Physics>>) {
+    time.pause();
+}
+
+fn resume_physics(mut time: ResMut<Time<Physics>>) {
+    time.unpause();
+}
+
 // #[expect(clippy::needless_pass_by_ref_mut, reason = "Needed for debug_physics feature")]
+#[cfg_attr(
+    not(feature = "debug_physics"),
+    expect(
+        clippy::missing_const_for_fn,
+        reason = "Not const with debug_physics feature"
+    )
+)]
 fn enable_debug(_app: &mut App) {
     #[cfg(feature = "debug_physics")]
     {
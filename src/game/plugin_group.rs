@@ -4,8 +4,13 @@ use bevy::{
 };
 
 use super::{
-    network_plugin::NetworkingPlugin, physics_plugin::PhysicsPlugin, ship_plugin::ShipPlugin,
-    states_plugin::StatesPlugin,
+    asteroid_plugin::AsteroidPlugin, collider_plugin::ColliderPlugin,
+    economy_plugin::EconomyPlugin, exploration_plugin::ExplorationPlugin,
+    flight_script_plugin::FlightScriptPlugin, formation_plugin::FormationPlugin,
+    inventory_plugin::InventoryPlugin, loading_plugin::LoadingPlugin,
+    mission_plugin::MissionPlugin, network_plugin::NetworkingPlugin, physics_plugin::PhysicsPlugin,
+    replay_plugin::ReplayPlugin, save_plugin::SavePlugin, ship_plugin::ShipPlugin,
+    states_plugin::StatesPlugin, targeting_plugin::TargetingPlugin, world_plugin::WorldPlugin,
 };
 
 #[expect(clippy::module_name_repetitions, reason = "This is a plugin group for the game")]
@@ -15,9 +20,22 @@ impl PluginGroup for GamePluginGroup {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(StatesPlugin)
+            .add(LoadingPlugin)
+            .add(ColliderPlugin)
             .add(PhysicsPlugin)
             .add(NetworkingPlugin)
             .add(ShipPlugin)
+            .add(FlightScriptPlugin)
+            .add(FormationPlugin)
+            .add(TargetingPlugin)
+            .add(InventoryPlugin)
+            .add(WorldPlugin)
+            .add(AsteroidPlugin)
+            .add(ExplorationPlugin)
+            .add(EconomyPlugin)
+            .add(MissionPlugin)
+            .add(SavePlugin)
+            .add(ReplayPlugin)
             .add(LogDiagnosticsPlugin::default())
     }
 }
@@ -0,0 +1,456 @@
+use core::error::Error;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use derive_more::Display;
+use ron::error::SpannedError;
+use serde::{Deserialize, Serialize};
+
+use avian3d::prelude::*;
+
+use bevy::prelude::*;
+
+use crate::cli::CommandLineArguments;
+
+use super::{
+    exploration_plugin::DiscoveryJournal,
+    formation_plugin::FormationMember,
+    save_plugin::{ship_key, PendingLoad, SaveGame, SavedShipReadOnly, ShipKey},
+    ship_plugin::{ActionEventData, Controller, PlayerId},
+    states_plugin::{simulation_running, FrameSystemsSet, GameModeState, MainState},
+    world_plugin::WorldSeed,
+};
+
+#[derive(Debug)]
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(Startup, start_from_command_line)
+            .add_systems(
+                FixedUpdate,
+                feed_replay_inputs
+                    .run_if(resource_exists::<Replay>)
+                    .run_if(in_state(GameModeState::Singleplayer))
+                    .in_set(FrameSystemsSet::Input),
+            )
+            .add_systems(
+                FixedUpdate,
+                // The state the tick ended with, once physics has moved the ships
+                (
+                    record_tick.run_if(resource_exists::<Recorder>),
+                    verify_replay.run_if(resource_exists::<Replay>),
+                )
+                    .after(FrameSystemsSet::Progression)
+                    .after(PhysicsSet::Sync)
+                    .run_if(in_state(MainState::InGame))
+                    .run_if(simulation_running),
+            )
+            .add_systems(
+                OnExit(MainState::InGame),
+                write_recording.run_if(resource_exists::<Recorder>),
+            )
+            .add_systems(
+                Last,
+                write_recording
+                    .run_if(resource_exists::<Recorder>)
+                    .run_if(on_event::<AppExit>()),
+            );
+    }
+}
+
+/// Bumped whenever [`Recording`] changes shape, or the same inputs would fly or checksum
+/// differently, older recordings are rejected rather than misread.
+pub const RECORDING_VERSION: u32 = 4;
+
+/// Ticks between checksums, frequent enough to tell roughly where a replay went wrong.
+const CHECKSUM_INTERVAL: u64 = 60;
+
+#[derive(Debug, Display)]
+pub enum RecordingError {
+    #[display("Could not access recording: {_0}")]
+    Io(io::Error),
+    #[display("Could not write recording: {_0}")]
+    Serialize(ron::Error),
+    #[display("Could not read recording: {_0}")]
+    Parse(SpannedError),
+    #[display("Recording version {found} is not supported, expected {RECORDING_VERSION}")]
+    UnsupportedVersion { found: u32 },
+}
+
+impl Error for RecordingError {}
+
+/// Every input of a session, enough to fly it again tick for tick.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Recording {
+    pub version: u32,
+    /// Number of leaders in the session, each with their own formation.
+    pub players: u8,
+    /// The world seed and every ship as they were before the first recorded tick.
+    pub initial_state: SaveGame,
+    pub ticks: u64,
+    /// Inputs only on the ticks they changed, ships keep flying with their last one in between.
+    pub inputs: BTreeMap<ShipKey, Vec<(u64, ActionEventData)>>,
+    /// Checksums of every ship's motion at the end of a tick.
+    pub checksums: BTreeMap<u64, u64>,
+}
+
+/// Just enough of a recording to check its version before trusting the rest of it.
+#[derive(Deserialize)]
+struct RecordingHeader {
+    version: u32,
+}
+
+impl Recording {
+    pub const fn new(players: u8, initial_state: SaveGame) -> Self {
+        Self {
+            version: RECORDING_VERSION,
+            players,
+            initial_state,
+            ticks: 0,
+            inputs: BTreeMap::new(),
+            checksums: BTreeMap::new(),
+        }
+    }
+
+    /// Records the input a ship flew the current tick with.
+    pub fn record_input(&mut self, ship: ShipKey, input: ActionEventData) {
+        let inputs = self.inputs.entry(ship).or_default();
+        if inputs.last().is_none_or(|(_, last)| *last != input) {
+            inputs.push((self.ticks, input));
+        }
+    }
+
+    /// The input a ship flew a tick with, none before the ship's first recorded input.
+    pub fn input_at(&self, ship: &ShipKey, tick: u64) -> Option<ActionEventData> {
+        let inputs = self.inputs.get(ship)?;
+        let changes = inputs.partition_point(|(changed, _)| *changed <= tick);
+        inputs.get(changes.checked_sub(1)?).map(|(_, input)| *input)
+    }
+
+    /// Written without whitespace, recordings get long.
+    pub fn to_ron(&self) -> Result<String, RecordingError> {
+        ron::to_string(self).map_err(RecordingError::Serialize)
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Self, RecordingError> {
+        let header = ron::from_str::<RecordingHeader>(ron).map_err(RecordingError::Parse)?;
+        if header.version != RECORDING_VERSION {
+            return Err(RecordingError::UnsupportedVersion {
+                found: header.version,
+            });
+        }
+        ron::from_str(ron).map_err(RecordingError::Parse)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), RecordingError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(RecordingError::Io)?;
+        }
+        fs::write(path, self.to_ron()?).map_err(RecordingError::Io)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, RecordingError> {
+        Self::from_ron(&fs::read_to_string(path).map_err(RecordingError::Io)?)
+    }
+}
+
+/// Records the session to `path`, starting once the ships are there.
+#[derive(Resource, Debug)]
+struct Recorder {
+    path: PathBuf,
+    recording: Option<Recording>,
+}
+
+/// A recording being played back in place of the players' inputs.
+#[derive(Resource, Debug)]
+pub struct Replay {
+    recording: Recording,
+    tick: u64,
+    verified: usize,
+    diverged_on: Option<u64>,
+}
+
+impl Replay {
+    /// Number of leaders to spawn, the same as when the session was recorded.
+    pub const fn players(&self) -> u8 {
+        self.recording.players
+    }
+}
+
+fn start_from_command_line(mut commands: Commands, args: Option<Res<CommandLineArguments>>) {
+    let Some(args) = args else {
+        return;
+    };
+
+    if let Some(path) = &args.replay {
+        match Recording::read(path) {
+            Ok(recording) => {
+                info!(
+                    "Replaying {} ticks from {}",
+                    recording.ticks,
+                    path.display()
+                );
+                // The seed has to be in place before the world is generated on entering the game
+                commands.insert_resource(WorldSeed(recording.initial_state.world_seed));
+                commands.insert_resource(PendingLoad(recording.initial_state.clone()));
+                commands.insert_resource(Replay {
+                    recording,
+                    tick: 0,
+                    verified: 0,
+                    diverged_on: None,
+                });
+            }
+            Err(error) => error!(
+                "Starting a new game, {} failed to load: {error}",
+                path.display()
+            ),
+        }
+    }
+
+    if let Some(path) = &args.record {
+        commands.insert_resource(Recorder {
+            path: path.clone(),
+            recording: None,
+        });
+    }
+}
+
+/// 64-bit FNV-1a, spelled out so checksums stay the same across builds and Rust releases.
+#[derive(Debug, Clone, Copy)]
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    const fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_key(&mut self, key: ShipKey) {
+        match key {
+            ShipKey::Player(player) => self.write(&[0, player]),
+            ShipKey::Wingman { leader, slot } => {
+                self.write(&[1, leader]);
+                self.write(&u64::try_from(slot).unwrap_or(u64::MAX).to_le_bytes());
+            }
+        }
+    }
+}
+
+/// Fingerprint of where every ship is and how it moves, only equal when the simulations agree.
+fn checksum(ships: &Query<SavedShipReadOnly>, players: &Query<&PlayerId>) -> u64 {
+    let motions = ships
+        .iter()
+        .filter_map(|ship| {
+            let key = ship_key(ship.player, ship.member, players)?;
+            let motion = [
+                ship.transform.translation.to_array(),
+                ship.linear_velocity.0.to_array(),
+                ship.angular_velocity.0.to_array(),
+            ];
+            Some((key, (motion, ship.transform.rotation.to_array())))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let mut hasher = Fnv1a::new();
+    for (key, (motion, rotation)) in motions {
+        hasher.write_key(key);
+        for value in motion.iter().flatten().chain(&rotation) {
+            hasher.write(&value.to_bits().to_le_bytes());
+        }
+    }
+    hasher.0
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn record_tick(
+    mut recorder: ResMut<Recorder>,
+    world_seed: Res<WorldSeed>,
+    journal: Res<DiscoveryJournal>,
+    ships: Query<SavedShipReadOnly>,
+    inputs: Query<(
+        Option<&PlayerId>,
+        Option<&FormationMember>,
+        &ActionEventData,
+    )>,
+    players: Query<&PlayerId>,
+) {
+    // Ships are spawned on entering the game, wait for them to be there
+    if ships.is_empty() {
+        return;
+    }
+
+    let Some(recording) = &mut recorder.recording else {
+        // This tick has already been flown, the recording starts from where it left the ships
+        let player_count = players.iter().map(|player| player.0 + 1).max().unwrap_or(0);
+        recorder.recording = Some(Recording::new(
+            player_count,
+            SaveGame::capture(*world_seed, &journal, &ships, &players),
+        ));
+        return;
+    };
+
+    for (player, member, &input) in &inputs {
+        if let Some(key) = ship_key(player, member, &players) {
+            recording.record_input(key, input);
+        }
+    }
+    if recording.ticks % CHECKSUM_INTERVAL == 0 {
+        _ = recording
+            .checksums
+            .insert(recording.ticks, checksum(&ships, &players));
+    }
+    recording.ticks += 1;
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn write_recording(mut commands: Commands, recorder: Res<Recorder>) {
+    // Only the first game is recorded, so a later one can't overwrite the bug it shows
+    commands.remove_resource::<Recorder>();
+
+    let Some(recording) = &recorder.recording else {
+        return;
+    };
+    match recording.write(&recorder.path) {
+        Ok(()) => info!(
+            "Recorded {} ticks to {}",
+            recording.ticks,
+            recorder.path.display()
+        ),
+        Err(error) => error!("Failed to record to {}: {error}", recorder.path.display()),
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn feed_replay_inputs(
+    replay: Res<Replay>,
+    mut ships: Query<(
+        Option<&PlayerId>,
+        Option<&FormationMember>,
+        &Controller,
+        &mut ActionEventData,
+    )>,
+    players: Query<&PlayerId>,
+) {
+    for (player, member, controller, mut action_event_data) in &mut ships {
+        if *controller != Controller::Replay {
+            continue;
+        }
+        // Past the end of the recording every ship lets go of its controls
+        *action_event_data = ship_key(player, member, &players)
+            .filter(|_| replay.tick < replay.recording.ticks)
+            .and_then(|key| replay.recording.input_at(&key, replay.tick))
+            .unwrap_or_default();
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn verify_replay(
+    mut replay: ResMut<Replay>,
+    ships: Query<SavedShipReadOnly>,
+    players: Query<&PlayerId>,
+) {
+    // The starting state is loaded with the ships, ticks before that aren't part of the replay
+    if ships.is_empty() || replay.tick >= replay.recording.ticks {
+        return;
+    }
+
+    let tick = replay.tick;
+    if let Some(&expected) = replay.recording.checksums.get(&tick) {
+        if checksum(&ships, &players) == expected {
+            replay.verified += 1;
+        } else if replay.diverged_on.is_none() {
+            error!("Replay diverged from the recording on tick {tick}");
+            replay.diverged_on = Some(tick);
+        }
+    }
+    replay.tick += 1;
+
+    if replay.tick == replay.recording.ticks {
+        let checksums = replay.recording.checksums.len();
+        match replay.diverged_on {
+            None => info!(
+                "Replay finished, all {checksums} checksums match the recording"
+            ),
+            Some(tick) => error!(
+                "Replay finished, {} of {checksums} checksums matched, first divergence on tick {tick}",
+                replay.verified
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thrust(thrust: f32) -> ActionEventData {
+        ActionEventData {
+            thrust,
+            ..default()
+        }
+    }
+
+    fn recording() -> Recording {
+        let mut recording = Recording::new(1, SaveGame::new(WorldSeed(42)));
+        for input in [thrust(0.0), thrust(1.0), thrust(1.0), thrust(0.5)] {
+            recording.record_input(ShipKey::Player(0), input);
+            recording.ticks += 1;
+        }
+        _ = recording.checksums.insert(0, 1234);
+        recording
+    }
+
+    #[test]
+    fn checksum_hash_matches_published_fnv1a_values() {
+        for (input, expected) in [
+            ("", 0xcbf2_9ce4_8422_2325),
+            ("a", 0xaf63_dc4c_8601_ec8c),
+            ("foobar", 0x8594_4171_f739_67e8),
+        ] {
+            let mut hasher = Fnv1a::new();
+            hasher.write(input.as_bytes());
+            assert_eq!(hasher.0, expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn only_changed_inputs_are_stored() {
+        let recording = recording();
+        assert_eq!(
+            recording.inputs.get(&ShipKey::Player(0)),
+            Some(&vec![(0, thrust(0.0)), (1, thrust(1.0)), (3, thrust(0.5))])
+        );
+    }
+
+    #[test]
+    fn inputs_hold_until_they_change() {
+        let recording = recording();
+        let ship = ShipKey::Player(0);
+        assert_eq!(recording.input_at(&ship, 2), Some(thrust(1.0)));
+        assert_eq!(recording.input_at(&ship, 3), Some(thrust(0.5)));
+        assert_eq!(recording.input_at(&ShipKey::Player(1), 0), None);
+    }
+
+    #[test]
+    fn ron_round_trip_preserves_everything() {
+        let recording = recording();
+        let ron = recording.to_ron().expect("Recording serialises");
+        assert_eq!(
+            Recording::from_ron(&ron).expect("Recording parses"),
+            recording
+        );
+    }
+}
@@ -0,0 +1,455 @@
+use core::error::Error;
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use derive_more::Display;
+use ron::{
+    error::SpannedError,
+    ser::{to_string_pretty, PrettyConfig},
+};
+use serde::{Deserialize, Serialize};
+
+use avian3d::prelude::*;
+
+use bevy::{ecs::query::QueryData, prelude::*};
+
+use crate::cli::CommandLineArguments;
+
+use super::{
+    economy_plugin::Credits,
+    exploration_plugin::DiscoveryJournal,
+    formation_plugin::FormationMember,
+    inventory_plugin::{CargoHold, ItemAssets, ItemCatalog, ItemId},
+    ship_plugin::{PlayerId, Ship},
+    states_plugin::{FrameSystemsSet, MainState},
+    world_plugin::WorldSeed,
+};
+
+#[derive(Debug)]
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_event::<SaveRequested>()
+            .add_systems(Startup, load_from_command_line)
+            .add_systems(
+                FixedUpdate,
+                apply_pending_load
+                    .run_if(resource_exists::<PendingLoad>)
+                    .in_set(FrameSystemsSet::World),
+            )
+            .add_systems(
+                Update,
+                save_game
+                    .run_if(on_event::<SaveRequested>())
+                    .run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+/// Bumped whenever [`SaveGame`] changes shape, older saves are rejected rather than misread.
+pub const SAVE_VERSION: u32 = 2;
+
+pub const QUICKSAVE_PATH: &str = "saves/quicksave.ron";
+
+#[derive(Debug, Display)]
+pub enum SaveError {
+    #[display("Could not access save file: {_0}")]
+    Io(io::Error),
+    #[display("Could not write save: {_0}")]
+    Serialize(ron::Error),
+    #[display("Could not read save: {_0}")]
+    Parse(SpannedError),
+    #[display("Save version {found} is not supported, expected {SAVE_VERSION}")]
+    UnsupportedVersion { found: u32 },
+}
+
+impl Error for SaveError {}
+
+/// Identifies a ship across sessions, entities are not stable so ships are matched up by who flies them.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShipKey {
+    Player(u8),
+    Wingman { leader: u8, slot: usize },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipSave {
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub linear_velocity: [f32; 3],
+    pub angular_velocity: [f32; 3],
+    pub auto_balance: bool,
+    pub color: [f32; 4],
+    pub credits: u64,
+    pub cargo: BTreeMap<ItemId, u32>,
+}
+
+impl ShipSave {
+    pub fn capture(
+        ship: &Ship,
+        transform: &Transform,
+        linear_velocity: &LinearVelocity,
+        angular_velocity: &AngularVelocity,
+        credits: Credits,
+        cargo_hold: &CargoHold,
+    ) -> Self {
+        Self {
+            translation: transform.translation.to_array(),
+            rotation: transform.rotation.to_array(),
+            linear_velocity: linear_velocity.0.to_array(),
+            angular_velocity: angular_velocity.0.to_array(),
+            auto_balance: ship.auto_balance,
+            color: ship.color.to_srgba().to_f32_array(),
+            credits: credits.0,
+            cargo: cargo_hold
+                .items()
+                .map(|(item, quantity)| (item.clone(), quantity))
+                .collect(),
+        }
+    }
+
+    /// Overwrites the ship's state with the saved one, cargo the catalog no longer knows is dropped.
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "One argument per saved component"
+    )]
+    pub fn apply(
+        &self,
+        ship: &mut Ship,
+        transform: &mut Transform,
+        linear_velocity: &mut LinearVelocity,
+        angular_velocity: &mut AngularVelocity,
+        credits: &mut Credits,
+        cargo_hold: &mut CargoHold,
+        catalog: &ItemCatalog,
+    ) {
+        transform.translation = Vec3::from_array(self.translation);
+        transform.rotation = Quat::from_array(self.rotation);
+        linear_velocity.0 = Vec3::from_array(self.linear_velocity);
+        angular_velocity.0 = Vec3::from_array(self.angular_velocity);
+        ship.auto_balance = self.auto_balance;
+        ship.color = Srgba::from_f32_array(self.color).into();
+        credits.0 = self.credits;
+
+        _ = cargo_hold.take_all();
+        for (item, &quantity) in &self.cargo {
+            if let Err(error) = cargo_hold.add(item, quantity, catalog) {
+                warn!("Dropping saved cargo: {error}");
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SaveGame {
+    pub version: u32,
+    pub world_seed: u64,
+    pub ships: BTreeMap<ShipKey, ShipSave>,
+    pub journal: DiscoveryJournal,
+}
+
+/// Just enough of a save to check its version before trusting the rest of it.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+impl SaveGame {
+    pub fn new(world_seed: WorldSeed) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            world_seed: world_seed.0,
+            ships: BTreeMap::new(),
+            journal: DiscoveryJournal::default(),
+        }
+    }
+
+    /// Everything about the current game that goes into a save.
+    pub fn capture(
+        world_seed: WorldSeed,
+        journal: &DiscoveryJournal,
+        ships: &Query<SavedShipReadOnly>,
+        players: &Query<&PlayerId>,
+    ) -> Self {
+        let mut save = Self::new(world_seed);
+        save.journal = journal.clone();
+        for saved_ship in ships {
+            if let Some(key) = ship_key(saved_ship.player, saved_ship.member, players) {
+                _ = save.ships.insert(
+                    key,
+                    ShipSave::capture(
+                        saved_ship.ship,
+                        saved_ship.transform,
+                        saved_ship.linear_velocity,
+                        saved_ship.angular_velocity,
+                        *saved_ship.credits,
+                        saved_ship.cargo_hold,
+                    ),
+                );
+            }
+        }
+        save
+    }
+
+    pub fn to_ron(&self) -> Result<String, SaveError> {
+        to_string_pretty(self, PrettyConfig::default()).map_err(SaveError::Serialize)
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Self, SaveError> {
+        let header = ron::from_str::<SaveHeader>(ron).map_err(SaveError::Parse)?;
+        if header.version != SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion {
+                found: header.version,
+            });
+        }
+        ron::from_str(ron).map_err(SaveError::Parse)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(SaveError::Io)?;
+        }
+        fs::write(path, self.to_ron()?).map_err(SaveError::Io)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, SaveError> {
+        Self::from_ron(&fs::read_to_string(path).map_err(SaveError::Io)?)
+    }
+}
+
+/// Ask for the current game to be written to `path`.
+#[derive(Event, Debug, Clone)]
+pub struct SaveRequested {
+    pub path: String,
+}
+
+/// A save waiting for its ships to be spawned before it can be applied.
+#[derive(Resource, Debug, Clone)]
+pub struct PendingLoad(pub SaveGame);
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn load_from_command_line(mut commands: Commands, args: Option<Res<CommandLineArguments>>) {
+    // A replay brings its own starting state
+    let Some(path) = args
+        .as_ref()
+        .filter(|args| args.replay.is_none())
+        .and_then(|args| args.load.as_ref())
+    else {
+        return;
+    };
+
+    match SaveGame::read(path) {
+        Ok(save) => {
+            info!("Loading save {}", path.display());
+            // The seed has to be in place before the world is generated on entering the game
+            commands.insert_resource(WorldSeed(save.world_seed));
+            commands.insert_resource(PendingLoad(save));
+        }
+        Err(error) => error!(
+            "Starting a new game, {} failed to load: {error}",
+            path.display()
+        ),
+    }
+}
+
+/// Everything about a ship that goes into a save.
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct SavedShip {
+    pub player: Option<&'static PlayerId>,
+    pub member: Option<&'static FormationMember>,
+    pub ship: &'static mut Ship,
+    pub transform: &'static mut Transform,
+    pub linear_velocity: &'static mut LinearVelocity,
+    pub angular_velocity: &'static mut AngularVelocity,
+    pub credits: &'static mut Credits,
+    pub cargo_hold: &'static mut CargoHold,
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn apply_pending_load(
+    mut commands: Commands,
+    pending_load: Res<PendingLoad>,
+    item_assets: Res<ItemAssets>,
+    catalogs: Res<Assets<ItemCatalog>>,
+    mut journal: ResMut<DiscoveryJournal>,
+    mut ships: Query<SavedShip>,
+    players: Query<&PlayerId>,
+) {
+    let Some(catalog) = item_assets.catalog(&catalogs) else {
+        return;
+    };
+    // Ships are spawned on entering the game, wait for them to be there
+    if ships.is_empty() {
+        return;
+    }
+
+    for mut saved_ship in &mut ships {
+        let Some(key) = ship_key(saved_ship.player, saved_ship.member, &players) else {
+            continue;
+        };
+        if let Some(ship_save) = pending_load.0.ships.get(&key) {
+            ship_save.apply(
+                &mut saved_ship.ship,
+                &mut saved_ship.transform,
+                &mut saved_ship.linear_velocity,
+                &mut saved_ship.angular_velocity,
+                &mut saved_ship.credits,
+                &mut saved_ship.cargo_hold,
+                catalog,
+            );
+        }
+    }
+
+    journal.clone_from(&pending_load.0.journal);
+    commands.remove_resource::<PendingLoad>();
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn save_game(
+    mut save_requests: EventReader<SaveRequested>,
+    world_seed: Res<WorldSeed>,
+    journal: Res<DiscoveryJournal>,
+    ships: Query<SavedShipReadOnly>,
+    players: Query<&PlayerId>,
+) {
+    let save = SaveGame::capture(*world_seed, &journal, &ships, &players);
+    for request in save_requests.read() {
+        match save.write(Path::new(&request.path)) {
+            Ok(()) => info!("Saved game to {}", request.path),
+            Err(error) => error!("Failed to save game to {}: {error}", request.path),
+        }
+    }
+}
+
+/// The key a ship is saved and recorded under.
+pub fn ship_key(
+    player: Option<&PlayerId>,
+    member: Option<&FormationMember>,
+    players: &Query<&PlayerId>,
+) -> Option<ShipKey> {
+    match (player, member) {
+        (Some(player), _) => Some(ShipKey::Player(player.0)),
+        (None, Some(member)) => players
+            .get(member.leader)
+            .ok()
+            .map(|leader| ShipKey::Wingman {
+                leader: leader.0,
+                slot: member.slot,
+            }),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::game::inventory_plugin::ItemDefinition;
+
+    fn catalog() -> ItemCatalog {
+        ItemCatalog {
+            items: BTreeMap::from([(
+                ItemId("iron_ore".to_owned()),
+                ItemDefinition {
+                    name: "Iron Ore".to_owned(),
+                    unit_volume: 1.0,
+                    unit_mass: 2.0,
+                },
+            )]),
+        }
+    }
+
+    fn ship_save() -> ShipSave {
+        ShipSave {
+            translation: [1.0, -2.5, 300.0],
+            rotation: Quat::from_rotation_y(0.75).to_array(),
+            linear_velocity: [0.0, 4.0, -8.0],
+            angular_velocity: [0.1, 0.0, 0.0],
+            auto_balance: false,
+            color: [0.2, 0.4, 0.6, 1.0],
+            credits: 1234,
+            cargo: BTreeMap::from([(ItemId("iron_ore".to_owned()), 7)]),
+        }
+    }
+
+    fn save_game() -> SaveGame {
+        let mut save = SaveGame::new(WorldSeed(42));
+        _ = save.ships.insert(ShipKey::Player(0), ship_save());
+        _ = save
+            .ships
+            .insert(ShipKey::Wingman { leader: 0, slot: 1 }, ship_save());
+        _ = save.journal.record("Derelict Freighter", PlayerId(0));
+        _ = save.journal.record("Derelict Freighter", PlayerId(1));
+        save
+    }
+
+    #[test]
+    fn ron_round_trip_preserves_everything() {
+        let save = save_game();
+        let ron = save.to_ron().expect("Save serialises");
+        assert_eq!(SaveGame::from_ron(&ron).expect("Save parses"), save);
+    }
+
+    #[test]
+    fn file_round_trip_preserves_everything() {
+        let path = env::temp_dir()
+            .join(format!("spacerama-save-test-{}", process::id()))
+            .join("round_trip.ron");
+        let save = save_game();
+        save.write(&path).expect("Save is written");
+        let loaded = SaveGame::read(&path);
+        _ = fs::remove_dir_all(path.parent().expect("Save has a directory"));
+        assert_eq!(loaded.expect("Save is read back"), save);
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let mut save = save_game();
+        save.version = SAVE_VERSION + 1;
+        let ron = save.to_ron().expect("Save serialises");
+        assert!(matches!(
+            SaveGame::from_ron(&ron),
+            Err(SaveError::UnsupportedVersion { found }) if found == SAVE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn ship_state_round_trips_through_components() {
+        let catalog = catalog();
+        let saved = ship_save();
+
+        let mut ship = Ship {
+            auto_balance: true,
+            color: Color::WHITE,
+        };
+        let mut transform = Transform::default();
+        let mut linear_velocity = LinearVelocity::default();
+        let mut angular_velocity = AngularVelocity::default();
+        let mut credits = Credits(0);
+        let mut cargo_hold = CargoHold::new(100.0, 100.0);
+        saved.apply(
+            &mut ship,
+            &mut transform,
+            &mut linear_velocity,
+            &mut angular_velocity,
+            &mut credits,
+            &mut cargo_hold,
+            &catalog,
+        );
+
+        assert_eq!(cargo_hold.quantity(&ItemId("iron_ore".to_owned())), 7);
+        assert!((cargo_hold.mass() - 14.0).abs() < f32::EPSILON);
+        let captured = ShipSave::capture(
+            &ship,
+            &transform,
+            &linear_velocity,
+            &angular_velocity,
+            credits,
+            &cargo_hold,
+        );
+        assert_eq!(captured, saved);
+    }
+}
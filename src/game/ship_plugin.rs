@@ -1,13 +1,30 @@
 use core::f32::consts::FRAC_PI_2;
 use derive_more::AddAssign;
+use derive_more::Display;
 use derive_more::Mul;
+use serde::{Deserialize, Serialize};
 
 use avian3d::prelude::*;
 
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
-use super::states_plugin::{FrameSystemsSet, InGameState, MainState};
+use crate::cli::{ColliderKind, CommandLineArguments};
+
+use super::{
+    asteroid_plugin::MiningLaser,
+    collider_plugin::{ColliderCache, ColliderError, ColliderStrategy},
+    economy_plugin::{Credits, Trader, STARTING_CREDITS},
+    exploration_plugin::Scanner,
+    flight_script_plugin::PlayerFlightScript,
+    formation_plugin::{Formation, FormationMember},
+    inventory_plugin::CargoHold,
+    loading_plugin::{LoadingAppExt, LoadingProgress, PrepareLoadedAssetsSet},
+    network_plugin::NetworkSession,
+    replay_plugin::Replay,
+    states_plugin::{simulation_running, FrameSystemsSet, MainState},
+    targeting_plugin::{CurrentTarget, Targetable},
+};
 
 #[derive(Debug)]
 pub struct ShipPlugin;
@@ -15,16 +32,26 @@ pub struct ShipPlugin;
 impl Plugin for ShipPlugin {
     fn build(&self, app: &mut App) {
         _ = app
-            .configure_loading_state(
-                LoadingStateConfig::new(MainState::Loading).load_collection::<ShipAssets>(),
+            .load_tracked_collection::<ShipAssets>()
+            .add_systems(
+                Update,
+                build_ship_collider
+                    .run_if(resource_exists::<ShipAssets>)
+                    .run_if(not(resource_exists::<ShipCollider>))
+                    .run_if(not(resource_exists::<ShipColliderFailed>))
+                    .in_set(PrepareLoadedAssetsSet),
             )
+            // Tried again next time loading starts, after the failure has been shown
+            .add_systems(OnExit(MainState::Loading), forget_ship_collider_failure)
             .add_systems(OnEnter(MainState::InGame), setup)
+            // Rebuilt from the collider cache next time, in case the collider settings changed
+            .add_systems(OnExit(MainState::InGame), remove_ship_collider)
             .add_systems(
                 FixedUpdate,
                 process_actions
                     .in_set(FrameSystemsSet::Player)
                     .run_if(in_state(MainState::InGame))
-                    .run_if(in_state(InGameState::Running)),
+                    .run_if(simulation_running),
             );
     }
 }
@@ -35,10 +62,51 @@ struct ShipAssets {
     ship_001_main: Handle<Mesh>,
 }
 
+/// The collider every ship in this session shares, built from the ship mesh once it has loaded.
+#[derive(Resource, Debug)]
+struct ShipCollider(Collider);
+
+/// Marks the ship collider as unbuildable, so the failure is only reported once per load.
+#[derive(Resource, Debug)]
+struct ShipColliderFailed;
+
 #[derive(Component)]
 pub struct Ship {
-    auto_balance: bool,
-    color: Color,
+    pub auto_balance: bool,
+    pub color: Color,
+}
+
+/// Identifies the player flying a ship, stable across peers and sessions unlike an [`Entity`].
+#[derive(
+    Component,
+    Debug,
+    Display,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[display("Player {_0}")]
+pub struct PlayerId(pub u8);
+
+/// Where a ship's [`ActionEventData`] comes from each frame.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    /// A player on this machine, through their input devices.
+    LocalPlayer,
+    /// The game itself, e.g. a wingman following its formation.
+    Ai,
+    /// A player on another peer, whose inputs arrive through the session.
+    Remote,
+    /// Previously recorded inputs being played back.
+    Replay,
+    /// A flight script of timed inputs.
+    Scripted,
 }
 
 // Player
@@ -46,28 +114,41 @@ pub struct Ship {
 pub struct ShipBundle {
     ship: Ship,
     spatial: SpatialBundle,
+    state_scoped: StateScoped<MainState>,
     rigid_body: RigidBody,
     collider: Collider,
     mass_properties: MassPropertiesBundle,
+    targetable: Targetable,
+    current_target: CurrentTarget,
+    cargo_hold: CargoHold,
+    mining_laser: MiningLaser,
+    scanner: Scanner,
+    credits: Credits,
+    trader: Trader,
 }
 
 impl ShipBundle {
-    fn new(mesh: &Mesh, color: Color) -> Self {
+    fn new(collider: &Collider, color: Color, transform: Transform) -> Self {
         let ship = Ship {
             auto_balance: true,
             color,
         };
-        let spatial = SpatialBundle::from_transform(Transform::from_xyz(0.0, 0.0, 0.0));
-        let collider = Collider::convex_decomposition_from_mesh(mesh)
-            .expect("Failed to create collider from ship_001 mesh");
-        let mass_properties =
-            MassPropertiesBundle::new_computed(&collider, SHIP_MASS_DENSITY_SCALE);
+        let spatial = SpatialBundle::from_transform(transform);
+        let mass_properties = MassPropertiesBundle::new_computed(collider, SHIP_MASS_DENSITY_SCALE);
         Self {
             ship,
             spatial,
+            state_scoped: StateScoped(MainState::InGame),
             rigid_body: RigidBody::Dynamic,
-            collider,
+            collider: collider.clone(),
             mass_properties,
+            targetable: Targetable,
+            current_target: CurrentTarget::default(),
+            cargo_hold: CargoHold::new(SHIP_CARGO_VOLUME, SHIP_CARGO_MASS),
+            mining_laser: MiningLaser::default(),
+            scanner: Scanner::default(),
+            credits: Credits(STARTING_CREDITS),
+            trader: Trader::default(),
             // CollisionLayers::new([Layer::Bots], [Layer::Ground, Layer::Constructed]), // Bots collides with ground, and constructed layers
             // Friction::new(0.0),
             // Restitution::new(0.0).with_combine_rule(CoefficientCombine::Multiply),
@@ -78,25 +159,159 @@ impl ShipBundle {
 }
 
 #[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
-fn setup(mut commands: Commands, ship_assets: Res<ShipAssets>, assets_mesh: Res<Assets<Mesh>>) {
+fn build_ship_collider(
+    mut commands: Commands,
+    args: Option<Res<CommandLineArguments>>,
+    ship_assets: Res<ShipAssets>,
+    assets_mesh: Res<Assets<Mesh>>,
+    mut collider_cache: ResMut<ColliderCache>,
+    mut progress: ResMut<LoadingProgress>,
+) {
+    let strategy = args.map_or_else(
+        || ColliderStrategy::from(ColliderKind::ConvexDecomposition),
+        |args| args.ship_collider.into(),
+    );
+    let transform = Transform::from_rotation(Quat::from_rotation_y(FRAC_PI_2));
+    // Ships are dynamic bodies
+    let collider = if strategy.supports_dynamic_bodies() {
+        collider_cache.get_or_build(
+            &assets_mesh,
+            &ship_assets.ship_001_main,
+            transform,
+            &strategy,
+        )
+    } else {
+        Err(ColliderError::StaticOnly)
+    };
+    let collider = collider.or_else(|error| {
+        warn!("{error}, falling back to a box collider");
+        collider_cache.get_or_build(
+            &assets_mesh,
+            &ship_assets.ship_001_main,
+            transform,
+            &ColliderStrategy::BOUNDING_BOX,
+        )
+    });
+    match collider {
+        Ok(collider) => commands.insert_resource(ShipCollider(collider)),
+        Err(error) => {
+            error!("Could not build the ship collider: {error}");
+            progress.report_failure(format!("Ship collider: {error}"));
+            commands.insert_resource(ShipColliderFailed);
+        }
+    }
+}
+
+fn forget_ship_collider_failure(mut commands: Commands) {
+    commands.remove_resource::<ShipColliderFailed>();
+}
+
+fn remove_ship_collider(mut commands: Commands) {
+    commands.remove_resource::<ShipCollider>();
+}
+
+/// Distance between the local players' starting positions, leaving room for their formations.
+const LOCAL_PLAYER_SPACING: f32 = 80.0;
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn setup(
+    mut commands: Commands,
+    args: Option<Res<CommandLineArguments>>,
+    session: Option<Res<NetworkSession>>,
+    replay: Option<Res<Replay>>,
+    flight_script: Option<Res<PlayerFlightScript>>,
+    ship_collider: Option<Res<ShipCollider>>,
+    mut next_main_state: ResMut<NextState<MainState>>,
+) {
     // Spaceship setup
-    if let Some(ship_001) = assets_mesh.get(&ship_assets.ship_001_main) {
-        // let collider = Collider::capsule(4.0, 1.0);
-        // let collider = Collider::round_cuboid(10.5, 10.5, 5.5, 0.5);
-        // Generate pseudo random color from client id.
-        let h = 0.0; // (((mesh.to_bits().wrapping_mul(30)) % 360) as f32) / 360.0;
-        let s = 0.8;
-        let l = 0.5;
-        let color = Color::hsl(h, s, l);
-
-        let mesh = ship_001
-            .clone()
-            .transformed_by(Transform::from_rotation(Quat::from_rotation_y(FRAC_PI_2)));
-        _ = commands.spawn(ShipBundle::new(&mesh, color));
+    let Some(ship_collider) = ship_collider else {
+        // Loading shouldn't finish without it, there is no game to play without ships though
+        error!("The ship collider is missing, returning to the main menu");
+        next_main_state.set(MainState::MainMenu);
+        return;
+    };
+    let ShipCollider(collider) = ship_collider.as_ref();
+    let local_players = args.map_or(1, |args| args.local_players);
+    let networked = session.is_some();
+    // Local players take the first seats of the session, the other peers fly the rest
+    let players = session.map_or(local_players, |session| {
+        session.player_count.max(local_players)
+    });
+    // A replay flies every ship that was in the recording, wingmen included
+    let replaying = replay.is_some();
+    let players = replay.map_or(players, |replay| replay.players());
+    let wingman_controller = if replaying {
+        Controller::Replay
+    } else {
+        Controller::Ai
+    };
+    let mut color_index = 0;
+
+    for player in 0..players {
+        let script = flight_script
+            .as_ref()
+            // Peers couldn't follow a script only this machine has
+            .filter(|_| player == 0 && !replaying && !networked)
+            .map(|script| script.0.clone());
+        let controller = if replaying {
+            Controller::Replay
+        } else if script.is_some() {
+            Controller::Scripted
+        } else if player < local_players {
+            Controller::LocalPlayer
+        } else {
+            Controller::Remote
+        };
+        let formation = Formation::default();
+        let slots = formation.slots.clone();
+        let leader_transform =
+            Transform::from_xyz(f32::from(player) * LOCAL_PLAYER_SPACING, 0.0, 0.0);
+
+        let mut leader = commands.spawn((
+            ShipBundle::new(collider, ship_color(color_index), leader_transform),
+            PlayerId(player),
+            controller,
+            ActionEventData::default(),
+            formation,
+        ));
+        if let Some(script) = script {
+            _ = leader.insert(script);
+        }
+        let leader = leader.id();
+        color_index += 1;
+
+        // Wingmen start in their slots so they don't spawn inside each other
+        for (slot, offset) in slots.into_iter().enumerate() {
+            let transform = leader_transform * Transform::from_translation(offset);
+            _ = commands.spawn((
+                ShipBundle::new(collider, ship_color(color_index), transform),
+                wingman_controller,
+                ActionEventData::default(),
+                FormationMember { leader, slot },
+            ));
+            color_index += 1;
+        }
     }
 }
 
-#[derive(Component, Copy, Clone, Default, Debug, Mul, AddAssign)]
+#[expect(clippy::cast_precision_loss, reason = "Ship indices are small")]
+const fn ship_color(index: usize) -> Color {
+    // Generate pseudo random color from ship index.
+    let h = ((index * 137) % 360) as f32; // (((mesh.to_bits().wrapping_mul(30)) % 360) as f32) / 360.0;
+    let s = 0.8;
+    let l = 0.5;
+    Color::hsl(h, s, l)
+}
+
+/// The direction the ship's nose points in, which is also the direction main thrusters push it.
+pub fn heading(transform: &Transform) -> Dir3 {
+    transform.back()
+}
+
+#[derive(
+    Component, Copy, Clone, Default, Debug, PartialEq, Mul, AddAssign, Serialize, Deserialize,
+)]
+#[serde(default)]
 // Define an event to represent the spawning of a bot
 pub struct ActionEventData {
     pub thrust: f32,
@@ -106,10 +321,25 @@ pub struct ActionEventData {
     pub action1: f32,
     pub action2: f32,
     pub auto_balance: f32,
+    pub form_up: f32,
+    pub break_formation: f32,
+    pub attack_my_target: f32,
+    pub target_nearest: f32,
+    pub target_next: f32,
+    pub target_previous: f32,
+    pub jettison: f32,
+    pub mining_laser: f32,
+    pub scan: f32,
+    pub market_next: f32,
+    pub market_buy: f32,
+    pub market_sell: f32,
 }
 
 const SHIP_MASS_DENSITY_SCALE: f32 = 0.25;
 
+const SHIP_CARGO_VOLUME: f32 = 100.0;
+const SHIP_CARGO_MASS: f32 = 400.0;
+
 const PROPULSION_THRUSTERS_STRENGTH: f32 = 10_000.0;
 const ANGULAR_THRUSTERS_STRENGTH: f32 = 10_000.0;
 
@@ -134,7 +364,7 @@ fn process_actions(
         }
 
         let propulsion_thrusters = ExternalImpulse::new(
-            transform.back() * action_event_data.thrust * PROPULSION_THRUSTERS_STRENGTH,
+            heading(transform) * action_event_data.thrust * PROPULSION_THRUSTERS_STRENGTH,
         );
 
         let roll = auto_balance(
@@ -196,3 +426,168 @@ fn auto_balance(
         input_value
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::{f32::consts::FRAC_PI_2, time::Duration};
+
+    use bevy::{scene::ScenePlugin, state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+    use super::*;
+    use crate::game::{
+        flight_script_plugin::{FlightScript, FlightScriptPlugin},
+        physics_plugin::PhysicsPlugin,
+        states_plugin::GameModeState,
+    };
+
+    /// Bevy's default fixed timestep, so every update runs exactly one tick.
+    const TICKS_PER_SECOND: u32 = 64;
+
+    /// A headless app with nothing but physics and the flight model.
+    fn flight_app() -> App {
+        let mut app = App::new();
+        _ = app
+            .add_plugins((
+                MinimalPlugins,
+                TransformPlugin,
+                AssetPlugin::default(),
+                ScenePlugin,
+                StatesPlugin,
+                PhysicsPlugin,
+                FlightScriptPlugin,
+            ))
+            .init_resource::<Assets<Mesh>>()
+            .insert_state(GameModeState::Singleplayer)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / f64::from(TICKS_PER_SECOND),
+            )))
+            .configure_sets(
+                FixedUpdate,
+                FrameSystemsSet::Input.before(FrameSystemsSet::Player),
+            )
+            .add_systems(FixedUpdate, process_actions.in_set(FrameSystemsSet::Player));
+        app
+    }
+
+    /// Spawns a ship about the size of the real one, flown by `script`.
+    fn spawn_ship(app: &mut App, script: &str) -> Entity {
+        let collider = Collider::cuboid(10.0, 5.0, 20.0);
+        let ship = app
+            .world_mut()
+            .spawn((
+                ShipBundle::new(&collider, Color::WHITE, Transform::IDENTITY),
+                ActionEventData::default(),
+            ))
+            .id();
+        // Physics takes a couple of ticks to pick up a new body, impulses before that are lost
+        for _ in 0..4 {
+            app.update();
+        }
+        _ = app
+            .world_mut()
+            .entity_mut(ship)
+            .insert(FlightScript::from_ron(script).expect("Flight script parses"));
+        ship
+    }
+
+    fn run_seconds(app: &mut App, seconds: u32) {
+        for _ in 0..seconds * TICKS_PER_SECOND {
+            app.update();
+        }
+    }
+
+    fn yaw_angle(app: &App, ship: Entity) -> f32 {
+        let transform = app
+            .world()
+            .get::<Transform>(ship)
+            .expect("Ship has a transform");
+        let heading = heading(transform);
+        // Turning right swings the nose from +Z towards -X
+        (-heading.x).atan2(heading.z)
+    }
+
+    fn angular_speed(app: &App, ship: Entity) -> f32 {
+        app.world()
+            .get::<AngularVelocity>(ship)
+            .expect("Ship has an angular velocity")
+            .0
+            .length()
+    }
+
+    #[test]
+    fn forward_burn_accelerates_along_the_heading() {
+        let mut app = flight_app();
+        let ship = spawn_ship(&mut app, "[(duration: 0.5, input: (thrust: 1.0))]");
+        run_seconds(&mut app, 1);
+
+        let world = app.world();
+        let mass = world.get::<Mass>(ship).expect("Ship has a mass").0;
+        let velocity = world
+            .get::<LinearVelocity>(ship)
+            .expect("Ship has a velocity")
+            .0;
+        let transform = world.get::<Transform>(ship).expect("Ship has a transform");
+
+        // Half a second of full thrust, one impulse per tick
+        let expected_speed = 32.0 * PROPULSION_THRUSTERS_STRENGTH / mass;
+        assert!(
+            velocity.abs_diff_eq(Vec3::Z * expected_speed, expected_speed * 0.01),
+            "expected {expected_speed} m/s forwards, got {velocity}"
+        );
+        assert!(transform.rotation.abs_diff_eq(Quat::IDENTITY, 1e-4));
+        // Coasting for the second half covers half a second at full speed, burning less than that
+        let travelled = transform.translation;
+        assert!(travelled.xy().length() < 0.01, "drifted to {travelled}");
+        assert!(
+            (expected_speed * 0.5..expected_speed).contains(&travelled.z),
+            "travelled {travelled}"
+        );
+    }
+
+    #[test]
+    fn yaw_turns_ninety_degrees_and_stops() {
+        let mut app = flight_app();
+        let ship = spawn_ship(&mut app, "[(duration: 0.4375, input: (yaw: 1.0))]");
+        run_seconds(&mut app, 2);
+
+        let angle = yaw_angle(&app, ship);
+        assert!(
+            (angle - FRAC_PI_2).abs() < 3_f32.to_radians(),
+            "turned {} degrees",
+            angle.to_degrees()
+        );
+        assert!(angular_speed(&app, ship) < 1e-3);
+        let velocity = app
+            .world()
+            .get::<LinearVelocity>(ship)
+            .expect("Ship has a velocity")
+            .0;
+        assert!(velocity.length() < 1e-3, "drifting at {velocity}");
+    }
+
+    #[test]
+    fn auto_balance_stops_rotation() {
+        let mut app = flight_app();
+        let ship = spawn_ship(
+            &mut app,
+            "[
+                (duration: 0.015625, input: (auto_balance: 1.0)),
+                (duration: 0.25, input: (yaw: 1.0, roll: 0.5)),
+                (duration: 0.734375),
+                (duration: 0.015625, input: (auto_balance: 1.0)),
+            ]",
+        );
+
+        // Without auto balance the ship keeps spinning once the controls are let go
+        run_seconds(&mut app, 1);
+        let spinning = angular_speed(&app, ship);
+        assert!(spinning > 1.0, "spinning at {spinning} rad/s");
+
+        run_seconds(&mut app, 1);
+        assert!(
+            angular_speed(&app, ship) < 1e-3,
+            "still spinning at {} rad/s",
+            angular_speed(&app, ship)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy_asset_loader::loading_state::{LoadingState, LoadingStateAppExt};
 
+use crate::cli::{CommandLineArguments, GameMode};
+
 #[derive(Debug)]
 pub struct StatesPlugin;
 
@@ -8,17 +10,24 @@ impl Plugin for StatesPlugin {
     fn build(&self, app: &mut App) {
         _ = app
             .init_state::<MainState>()
+            .init_state::<GameModeState>()
             .init_state::<InGameState>()
+            .enable_state_scoped_entities::<MainState>()
+            .add_systems(Update, leave_startup.run_if(in_state(MainState::Startup)))
+            .add_systems(OnEnter(MainState::MainMenu), leave_game_mode)
+            .add_systems(OnExit(MainState::InGame), resume)
             .configure_sets(
                 Update,
                 (
                     FrameSystemsSet::World.before(FrameSystemsSet::Input),
                     FrameSystemsSet::Input.before(FrameSystemsSet::Player),
                     FrameSystemsSet::Player.before(FrameSystemsSet::Physics),
-                    FrameSystemsSet::Physics,
+                    FrameSystemsSet::Physics.before(FrameSystemsSet::Progression),
+                    FrameSystemsSet::Progression,
                 )
                     .run_if(in_state(MainState::InGame))
-                    .run_if(in_state(InGameState::Running)),
+                    .run_if(not(in_state(GameModeState::NotInGame)))
+                    .run_if(simulation_running),
             )
             .configure_sets(
                 FixedUpdate,
@@ -26,34 +35,43 @@ impl Plugin for StatesPlugin {
                     FrameSystemsSet::World.before(FrameSystemsSet::Input),
                     FrameSystemsSet::Input.before(FrameSystemsSet::Player),
                     FrameSystemsSet::Player.before(FrameSystemsSet::Physics),
-                    FrameSystemsSet::Physics,
+                    FrameSystemsSet::Physics.before(FrameSystemsSet::Progression),
+                    FrameSystemsSet::Progression,
                 )
                     .run_if(in_state(MainState::InGame))
-                    .run_if(in_state(InGameState::Running)),
+                    .run_if(not(in_state(GameModeState::NotInGame)))
+                    .run_if(simulation_running),
             )
-            .add_loading_state(
-                LoadingState::new(MainState::Loading).continue_to_state(MainState::InGame),
-            );
+            // Leaving the loading state is up to the loading plugin, so it can hold the loading screen
+            .add_loading_state(LoadingState::new(MainState::Loading));
     }
 }
 
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MainState {
-    // TODO: Add Startup and MainMenu in Phase 2 or later
-    // Startup,
-    // MainMenu,
     #[default]
+    Startup,
+    MainMenu,
     Loading,
     InGame,
 }
 
-// #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
-// pub enum GameModeState {
-//     #[default]
-//     NotInGame,
-//     Singleplayer,
-//     Multiplayer,
-// }
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GameModeState {
+    #[default]
+    NotInGame,
+    Singleplayer,
+    Multiplayer,
+}
+
+impl From<GameMode> for GameModeState {
+    fn from(mode: GameMode) -> Self {
+        match mode {
+            GameMode::Singleplayer => Self::Singleplayer,
+            GameMode::Multiplayer => Self::Multiplayer,
+        }
+    }
+}
 
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum InGameState {
@@ -68,4 +86,55 @@ pub enum FrameSystemsSet {
     Input,
     Player,
     Physics,
+    /// Reacts to what happened this frame, e.g. mission objectives.
+    Progression,
+}
+
+/// Goes to the main menu, or straight to loading the game when asked to on the command line.
+fn leave_startup(
+    args: Option<Res<CommandLineArguments>>,
+    mut next_main_state: ResMut<NextState<MainState>>,
+    mut next_game_mode_state: ResMut<NextState<GameModeState>>,
+) {
+    match args {
+        Some(args)
+            if args.skip_menu
+                || args.mode.is_some()
+                || args.load.is_some()
+                || args.replay.is_some() =>
+        {
+            // A replay already holds every player's inputs, there are no peers to play with
+            let mode = if args.replay.is_some() {
+                GameMode::Singleplayer
+            } else {
+                args.mode.unwrap_or(GameMode::Singleplayer)
+            };
+            next_game_mode_state.set(mode.into());
+            next_main_state.set(MainState::Loading);
+        }
+        _ => next_main_state.set(MainState::MainMenu),
+    }
+}
+
+fn leave_game_mode(mut next_game_mode_state: ResMut<NextState<GameModeState>>) {
+    next_game_mode_state.set(GameModeState::NotInGame);
+}
+
+fn resume(mut next_in_game_state: ResMut<NextState<InGameState>>) {
+    next_in_game_state.set(InGameState::Running);
+}
+
+/// Whether the game simulation should advance, pausing only stops it when nobody else is playing.
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+pub fn simulation_running(
+    in_game_state: Res<State<InGameState>>,
+    game_mode_state: Res<State<GameModeState>>,
+) -> bool {
+    *in_game_state.get() == InGameState::Running
+        || *game_mode_state.get() == GameModeState::Multiplayer
+}
+
+/// Puts a session scoped resource back to how it was before the game started.
+pub fn reset_resource<R: Resource + Default>(mut resource: ResMut<R>) {
+    *resource = R::default();
 }
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use itertools::Itertools;
+
+use super::{
+    ship_plugin::{heading, ActionEventData},
+    states_plugin::FrameSystemsSet,
+};
+
+#[derive(Debug)]
+pub struct TargetingPlugin;
+
+impl Plugin for TargetingPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_event::<TargetLockAcquired>()
+            .add_event::<TargetLockLost>()
+            .add_event::<ObjectScanned>()
+            .add_event::<Destroyed>()
+            .add_systems(
+                FixedUpdate,
+                (process_target_selection, update_target_locks)
+                    .chain()
+                    .in_set(FrameSystemsSet::Player),
+            );
+    }
+}
+
+/// Anything that can be selected as a target.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Targetable;
+
+/// The target a ship has selected and how far along the lock-on is.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct CurrentTarget {
+    pub entity: Option<Entity>,
+    /// Seconds the target has been kept inside the lock cone.
+    pub lock_progress: f32,
+    pub locked: bool,
+}
+
+impl CurrentTarget {
+    /// The target entity, only once the lock has been acquired.
+    pub const fn locked_entity(&self) -> Option<Entity> {
+        if self.locked {
+            self.entity
+        } else {
+            None
+        }
+    }
+
+    fn select(&mut self, entity: Option<Entity>) {
+        if self.entity != entity {
+            *self = Self {
+                entity,
+                ..default()
+            };
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TargetLockAcquired {
+    pub ship: Entity,
+    pub target: Entity,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TargetLockLost {
+    pub ship: Entity,
+    pub target: Entity,
+}
+
+/// Sent when a ship's sensors have had a good look at something.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ObjectScanned {
+    pub ship: Entity,
+    pub object: Entity,
+}
+
+/// Sent just before a targetable entity is despawned for good.
+#[derive(Event, Debug, Clone)]
+pub struct Destroyed {
+    pub entity: Entity,
+    /// The entity's [`Name`], captured since it can no longer be queried.
+    pub name: Option<String>,
+    /// Whoever dealt the final blow, if anyone.
+    pub attacker: Option<Entity>,
+}
+
+const TARGETING_RANGE: f32 = 2_000.0;
+const LOCK_CONE_HALF_ANGLE: f32 = 0.2; // radians
+pub const LOCK_DURATION: f32 = 1.5; // seconds
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn process_target_selection(
+    mut ships: Query<(Entity, &Transform, &ActionEventData, &mut CurrentTarget)>,
+    targetables: Query<(Entity, &Transform), With<Targetable>>,
+) {
+    for (ship, transform, action_event_data, mut current_target) in &mut ships {
+        let nearest = action_event_data.target_nearest.abs() > 0.5;
+        let next = action_event_data.target_next.abs() > 0.5;
+        let previous = action_event_data.target_previous.abs() > 0.5;
+        if !(nearest || next || previous) {
+            continue;
+        }
+
+        // Candidates ordered nearest first, cycling walks through them in that order
+        let candidates = targetables
+            .iter()
+            .filter(|&(entity, _)| entity != ship)
+            .map(|(entity, target_transform)| {
+                (
+                    entity,
+                    transform.translation.distance(target_transform.translation),
+                )
+            })
+            .filter(|&(_, distance)| distance <= TARGETING_RANGE)
+            .sorted_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(entity, _)| entity)
+            .collect_vec();
+
+        let current_index = current_target
+            .entity
+            .and_then(|entity| candidates.iter().position(|&candidate| candidate == entity));
+
+        let selected_index = match current_index {
+            _ if candidates.is_empty() => None,
+            Some(index) if next => Some((index + 1) % candidates.len()),
+            Some(index) if previous => Some((index + candidates.len() - 1) % candidates.len()),
+            None if previous => Some(candidates.len() - 1),
+            _ => Some(0),
+        };
+
+        current_target.select(selected_index.and_then(|index| candidates.get(index).copied()));
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_target_locks(
+    time: Res<Time>,
+    mut ships: Query<(Entity, &Transform, &mut CurrentTarget)>,
+    targetables: Query<&Transform, With<Targetable>>,
+    mut lock_acquired: EventWriter<TargetLockAcquired>,
+    mut lock_lost: EventWriter<TargetLockLost>,
+    mut object_scanned: EventWriter<ObjectScanned>,
+) {
+    for (ship, transform, mut current_target) in &mut ships {
+        let Some(target) = current_target.entity else {
+            continue;
+        };
+
+        let Ok(target_transform) = targetables.get(target) else {
+            // Target is gone, drop it entirely
+            if current_target.locked {
+                _ = lock_lost.send(TargetLockLost { ship, target });
+            }
+            current_target.select(None);
+            continue;
+        };
+
+        let offset = target_transform.translation - transform.translation;
+        let in_cone = offset.length() <= TARGETING_RANGE
+            && heading(transform).angle_between(offset) <= LOCK_CONE_HALF_ANGLE;
+
+        if in_cone {
+            current_target.lock_progress += time.delta_seconds();
+            if !current_target.locked && current_target.lock_progress >= LOCK_DURATION {
+                current_target.locked = true;
+                _ = lock_acquired.send(TargetLockAcquired { ship, target });
+                // Holding a lock long enough doubles as a sensor scan
+                _ = object_scanned.send(ObjectScanned {
+                    ship,
+                    object: target,
+                });
+            }
+        } else {
+            if current_target.locked {
+                _ = lock_lost.send(TargetLockLost { ship, target });
+            }
+            current_target.lock_progress = 0.0;
+            current_target.locked = false;
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use bevy_prng::WyRand;
+use rand_core::{RngCore, SeedableRng};
+
+use super::{
+    asteroid_plugin::AsteroidBundle,
+    exploration_plugin::{PointOfInterestBundle, PointOfInterestKind},
+    inventory_plugin::ItemId,
+    states_plugin::MainState,
+};
+
+#[derive(Debug)]
+pub struct WorldPlugin;
+
+impl Plugin for WorldPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .init_resource::<WorldSeed>()
+            .add_systems(OnEnter(MainState::InGame), generate_world);
+    }
+}
+
+/// Everything procedurally placed in the world derives from this, so peers sharing a seed share a world.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldSeed(pub u64);
+
+impl Default for WorldSeed {
+    fn default() -> Self {
+        Self(0x5EED_5EED)
+    }
+}
+
+const ASTEROID_COUNT: usize = 60;
+const ASTEROID_FIELD_RADIUS: f32 = 1_500.0;
+/// Keeps the spawn point clear so ships don't start inside a rock.
+const ASTEROID_FIELD_CLEARANCE: f32 = 150.0;
+const ASTEROID_MIN_RADIUS: f32 = 4.0;
+const ASTEROID_MAX_RADIUS: f32 = 20.0;
+
+const POINT_OF_INTEREST_COUNT: usize = 12;
+const POINT_OF_INTEREST_MIN_DISTANCE: f32 = 300.0;
+const POINT_OF_INTEREST_MAX_DISTANCE: f32 = 2_500.0;
+
+/// Ore found in asteroids, weighted by how common it is.
+const ASTEROID_RESOURCES: [(&str, f32); 3] =
+    [("iron_ore", 0.6), ("ice", 0.3), ("platinum_ore", 0.1)];
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn generate_world(mut commands: Commands, seed: Res<WorldSeed>) {
+    let mut rng = WyRand::seed_from_u64(seed.0);
+
+    for _ in 0..ASTEROID_COUNT {
+        let position =
+            random_position_in_shell(&mut rng, ASTEROID_FIELD_CLEARANCE, ASTEROID_FIELD_RADIUS);
+        let radius = ASTEROID_MIN_RADIUS.lerp(ASTEROID_MAX_RADIUS, random_unit(&mut rng));
+        let resource = random_resource(&mut rng);
+        _ = commands.spawn(AsteroidBundle::new(
+            resource,
+            radius,
+            Transform::from_translation(position),
+        ));
+    }
+
+    // Generated after the asteroids so the asteroid field takes the same values from the seed
+    // whether or not points of interest follow
+    for index in 0..POINT_OF_INTEREST_COUNT {
+        let position = random_position_in_shell(
+            &mut rng,
+            POINT_OF_INTEREST_MIN_DISTANCE,
+            POINT_OF_INTEREST_MAX_DISTANCE,
+        );
+        let kind = random_point_of_interest_kind(&mut rng);
+        _ = commands.spawn(PointOfInterestBundle::new(
+            kind,
+            format!("{kind} {}-{:02}", random_designation(&mut rng), index + 1),
+            Transform::from_translation(position),
+        ));
+    }
+}
+
+/// Uniform random value in `[0, 1]`.
+#[expect(clippy::cast_precision_loss, reason = "Only used for randomness")]
+pub fn random_unit(rng: &mut impl RngCore) -> f32 {
+    rng.next_u32() as f32 / u32::MAX as f32
+}
+
+/// Random point between `min_radius` and `max_radius` away from the origin.
+pub fn random_position_in_shell(rng: &mut impl RngCore, min_radius: f32, max_radius: f32) -> Vec3 {
+    let direction = (Vec3::new(random_unit(rng), random_unit(rng), random_unit(rng)) * 2.0
+        - Vec3::ONE)
+        .try_normalize()
+        .unwrap_or(Vec3::X);
+    direction * min_radius.lerp(max_radius, random_unit(rng))
+}
+
+fn random_resource(rng: &mut impl RngCore) -> ItemId {
+    let mut roll = random_unit(rng);
+    for (resource, weight) in ASTEROID_RESOURCES {
+        if roll <= weight {
+            return ItemId(resource.to_owned());
+        }
+        roll -= weight;
+    }
+    ItemId(ASTEROID_RESOURCES[0].0.to_owned())
+}
+
+fn random_point_of_interest_kind(rng: &mut impl RngCore) -> PointOfInterestKind {
+    let kinds = PointOfInterestKind::ALL;
+    usize::try_from(rng.next_u32())
+        .ok()
+        .and_then(|roll| kinds.get(roll % kinds.len()))
+        .copied()
+        .unwrap_or(PointOfInterestKind::Derelict)
+}
+
+/// Two letter catalogue designation, e.g. `KX`.
+fn random_designation(rng: &mut impl RngCore) -> String {
+    (0..2)
+        .map(|_| char::from(b'A' + u8::try_from(rng.next_u32() % 26).unwrap_or_default()))
+        .collect()
+}
@@ -1,4 +1,13 @@
+pub mod asteroid_plugin;
+pub mod camera_plugin;
+pub mod cargo_plugin;
+pub mod exploration_plugin;
+pub mod hud_plugin;
 pub mod input_plugin;
+pub mod loading_screen_plugin;
+pub mod menu_plugin;
 pub mod plugin_group;
 pub mod rendering_setup_plugin;
 pub mod ship_plugin;
+pub mod split_screen_plugin;
+pub mod station_plugin;
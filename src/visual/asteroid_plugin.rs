@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    asteroid_plugin::{Asteroid, MiningLaser},
+    states_plugin::MainState,
+};
+
+#[derive(Debug)]
+pub struct AsteroidPlugin;
+
+impl Plugin for AsteroidPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(OnEnter(MainState::InGame), setup)
+            .add_systems(
+                Update,
+                (on_asteroid_created_add_visuals, draw_mining_lasers)
+                    .run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct AsteroidVisuals {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(AsteroidVisuals {
+        mesh: meshes.add(Sphere::new(1.0).mesh().uv(24, 16)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.35, 0.3, 0.28),
+            perceptual_roughness: 0.95,
+            ..default()
+        }),
+    });
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn on_asteroid_created_add_visuals(
+    mut commands: Commands,
+    visuals: Res<AsteroidVisuals>,
+    query: Query<(Entity, &Asteroid), Added<Asteroid>>,
+) {
+    for (entity, asteroid) in query.iter() {
+        // Scaled on a child so the scale doesn't leak into the asteroid's collider
+        _ = commands.entity(entity).with_children(|parent| {
+            _ = parent.spawn(PbrBundle {
+                mesh: visuals.mesh.clone(),
+                material: visuals.material.clone(),
+                transform: Transform::from_scale(Vec3::splat(asteroid.radius)),
+                ..default()
+            });
+        });
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn draw_mining_lasers(mut gizmos: Gizmos, query: Query<(&Transform, &MiningLaser)>) {
+    for (transform, mining_laser) in &query {
+        if let Some(beam_end) = mining_laser.beam_end {
+            gizmos.line(transform.translation, beam_end, Color::srgb(1.0, 0.2, 0.1));
+        }
+    }
+}
@@ -0,0 +1,559 @@
+use core::f32::consts::{FRAC_PI_4, PI, TAU};
+
+use autodefault::autodefault;
+use avian3d::prelude::*;
+use bevy::{input::mouse::MouseMotion, prelude::*, transform::TransformSystem};
+use leafwing_input_manager::prelude::*;
+
+use super::{
+    input_plugin::{Action, FreeLook, GamepadAssignment, InputDevices, DEADZONE, FREE_LOOK_STICK},
+    split_screen_plugin::PlayerCamera,
+};
+use crate::game::{
+    ship_plugin::{heading, Controller, PlayerId},
+    states_plugin::MainState,
+};
+
+#[derive(Debug)]
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .init_resource::<CameraSettings>()
+            .add_systems(
+                Update,
+                (
+                    on_ship_created_add_camera,
+                    cycle_camera_modes,
+                    orbit_cameras,
+                )
+                    .chain()
+                    .run_if(in_state(MainState::InGame)),
+            )
+            .add_systems(
+                PostUpdate,
+                follow_ships
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+/// Longest step the springs are integrated with, longer frames are split up so they stay stable.
+const MAX_SPRING_STEP: f32 = 1.0 / 120.0;
+
+/// Keeps the orbit camera from flipping over the top of the ship.
+const MAX_ORBIT_PITCH: f32 = 1.4;
+
+/// Pulls a value after its goal like a weight on a spring, with a damper to settle it.
+#[derive(Debug, Clone, Copy)]
+pub struct Spring {
+    /// Oscillations per second without damping, higher follows more tightly.
+    pub frequency: f32,
+    /// 1 settles the quickest without overshooting, less lets it swing past the goal.
+    pub damping_ratio: f32,
+}
+
+impl Spring {
+    /// Acceleration for being `offset` away from the goal and moving `velocity` relative to it.
+    fn acceleration(self, offset: Vec3, velocity: Vec3) -> Vec3 {
+        let angular_frequency = TAU * self.frequency;
+        offset * angular_frequency.powi(2)
+            - velocity * (2.0 * self.damping_ratio * angular_frequency)
+    }
+}
+
+/// How the chase camera trails the ship it follows.
+#[derive(Debug, Clone)]
+pub struct ChaseCameraSettings {
+    /// Where the camera wants to be in the ship's space, behind and above it.
+    pub offset: Vec3,
+    pub position_spring: Spring,
+    pub rotation_spring: Spring,
+    /// Seconds of the ship's velocity the camera looks ahead by, to see where it's going.
+    pub look_ahead: f32,
+    /// Furthest the camera looks ahead of the ship, in meters.
+    pub max_look_ahead: f32,
+    /// Vertical field of view at rest, in radians.
+    pub fov: f32,
+    /// Vertical field of view from `fov_speed` on, widening with speed adds to the sense of it.
+    pub max_fov: f32,
+    pub fov_speed: f32,
+}
+
+impl Default for ChaseCameraSettings {
+    fn default() -> Self {
+        Self {
+            offset: Vec3::new(0.0, 4.5, -15.0),
+            position_spring: Spring {
+                frequency: 1.5,
+                damping_ratio: 0.9,
+            },
+            rotation_spring: Spring {
+                frequency: 2.5,
+                damping_ratio: 1.0,
+            },
+            look_ahead: 0.25,
+            max_look_ahead: 40.0,
+            fov: FRAC_PI_4,
+            max_fov: 75_f32.to_radians(),
+            fov_speed: 400.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CockpitSettings {
+    /// Moves the eye away from the ship's [`CockpitAnchor`], in the ship's space.
+    pub offset: Vec3,
+    pub fov: f32,
+}
+
+impl Default for CockpitSettings {
+    fn default() -> Self {
+        Self {
+            offset: Vec3::ZERO,
+            fov: 70_f32.to_radians(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OrbitSettings {
+    pub distance: f32,
+    /// Radians turned per pixel of mouse drag.
+    pub mouse_sensitivity: f32,
+    /// Radians per second turned with the stick all the way over.
+    pub stick_speed: f32,
+}
+
+impl Default for OrbitSettings {
+    fn default() -> Self {
+        Self {
+            distance: 30.0,
+            mouse_sensitivity: 0.005,
+            stick_speed: 2.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlyBySettings {
+    /// Seconds of the ship's velocity ahead of it that the camera waits at.
+    pub lead: f32,
+    /// Closest ahead of the ship the camera waits, and how far the ship gets past it before the
+    /// camera moves on.
+    pub min_distance: f32,
+    /// How far off the ship's path the camera waits, to the right and up.
+    pub side: f32,
+    pub height: f32,
+    pub fov: f32,
+}
+
+impl Default for FlyBySettings {
+    fn default() -> Self {
+        Self {
+            lead: 2.0,
+            min_distance: 60.0,
+            side: 15.0,
+            height: 5.0,
+            fov: 35_f32.to_radians(),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct CameraSettings {
+    pub chase: ChaseCameraSettings,
+    pub cockpit: CockpitSettings,
+    pub orbit: OrbitSettings,
+    pub fly_by: FlyBySettings,
+    /// Seconds the camera takes to move over when switching modes.
+    pub transition_duration: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            chase: ChaseCameraSettings::default(),
+            cockpit: CockpitSettings::default(),
+            orbit: OrbitSettings::default(),
+            fly_by: FlyBySettings::default(),
+            transition_duration: 0.6,
+        }
+    }
+}
+
+/// Where the pilot's eyes are in the ship's space, set by the ship's model.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CockpitAnchor(pub Vec3);
+
+/// How a player's camera looks at their ship, cycled through in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CameraMode {
+    /// Trailing the ship on springs.
+    #[default]
+    Chase,
+    /// From the pilot's seat.
+    Cockpit,
+    /// Circling the ship at a distance, turned with the right stick or a middle mouse drag.
+    Orbit,
+    /// Waiting ahead of the ship and watching it fly past.
+    Cinematic,
+}
+
+impl CameraMode {
+    const fn next(self) -> Self {
+        match self {
+            Self::Chase => Self::Cockpit,
+            Self::Cockpit => Self::Orbit,
+            Self::Orbit => Self::Cinematic,
+            Self::Cinematic => Self::Chase,
+        }
+    }
+}
+
+/// Where the chase camera would be, kept up to date in every mode so switching back is seamless.
+#[derive(Debug, Clone, Copy)]
+struct ChaseRig {
+    pose: Transform,
+    velocity: Vec3,
+    angular_velocity: Vec3,
+}
+
+/// The camera's pose and field of view when it started moving over to a new mode.
+#[derive(Debug, Clone, Copy)]
+struct Transition {
+    from: Transform,
+    from_fov: f32,
+    elapsed: f32,
+}
+
+/// A camera following a ship in one of the [`CameraMode`]s.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShipCamera {
+    pub target: Entity,
+    pub mode: CameraMode,
+    chase: ChaseRig,
+    /// Yaw and pitch around the ship in orbit mode, zero looks over the ship's tail.
+    orbit: Vec2,
+    /// Where the cinematic camera is waiting for the ship.
+    fly_by: Option<Vec3>,
+    transition: Option<Transition>,
+}
+
+impl ShipCamera {
+    const fn new(target: Entity, pose: Transform) -> Self {
+        Self {
+            target,
+            mode: CameraMode::Chase,
+            chase: ChaseRig {
+                pose,
+                velocity: Vec3::ZERO,
+                angular_velocity: Vec3::ZERO,
+            },
+            orbit: Vec2::ZERO,
+            fly_by: None,
+            transition: None,
+        }
+    }
+
+    /// Switches to `mode`, moving over from where the camera is now.
+    const fn switch_to(&mut self, mode: CameraMode, from: Transform, from_fov: f32) {
+        self.mode = mode;
+        self.orbit = Vec2::ZERO;
+        self.fly_by = None;
+        self.transition = Some(Transition {
+            from,
+            from_fov,
+            elapsed: 0.0,
+        });
+    }
+}
+
+/// Splits a frame into equal steps no longer than [`MAX_SPRING_STEP`].
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "Frame times are short and never negative"
+)]
+#[expect(clippy::cast_precision_loss, reason = "Step counts are small")]
+fn spring_steps(frame: f32) -> (u32, f32) {
+    let steps = (frame / MAX_SPRING_STEP).ceil().max(1.0) as u32;
+    (steps, frame / steps as f32)
+}
+
+fn chase_goal(ship: &Transform, settings: &ChaseCameraSettings) -> Transform {
+    Transform::from_translation(ship.transform_point(settings.offset))
+        .looking_at(ship.translation, ship.up())
+}
+
+/// Moves the chase rig after the ship on its springs.
+fn follow_chase(
+    rig: &mut ChaseRig,
+    ship: &Transform,
+    velocity: Vec3,
+    frame: f32,
+    settings: &ChaseCameraSettings,
+) {
+    let goal = ship.transform_point(settings.offset);
+    let look_ahead = (velocity * settings.look_ahead).clamp_length_max(settings.max_look_ahead);
+    let focus = ship.translation + look_ahead;
+
+    let (steps, delta) = spring_steps(frame);
+    for _ in 0..steps {
+        // Damping against the ship's velocity rather than none keeps the camera from trailing
+        // further behind the faster the ship flies, it only lags when the ship speeds up
+        let acceleration = settings
+            .position_spring
+            .acceleration(goal - rig.pose.translation, rig.velocity - velocity);
+        rig.velocity += acceleration * delta;
+        rig.pose.translation += rig.velocity * delta;
+
+        let goal_rotation = rig.pose.looking_at(focus, ship.up()).rotation;
+        let mut error = goal_rotation * rig.pose.rotation.inverse();
+        // The short way round
+        if error.w < 0.0 {
+            error = -error;
+        }
+        let angular_acceleration = settings
+            .rotation_spring
+            .acceleration(error.to_scaled_axis(), rig.angular_velocity);
+        rig.angular_velocity += angular_acceleration * delta;
+        rig.pose.rotation =
+            (Quat::from_scaled_axis(rig.angular_velocity * delta) * rig.pose.rotation).normalize();
+    }
+}
+
+fn cockpit_pose(ship: &Transform, anchor: Vec3, settings: &CockpitSettings) -> Transform {
+    // Cameras look down their -Z, ships fly towards their +Z
+    Transform::from_translation(ship.transform_point(anchor + settings.offset))
+        .with_rotation(ship.rotation * Quat::from_rotation_y(PI))
+}
+
+fn orbit_pose(ship: &Transform, orbit: Vec2, settings: &OrbitSettings) -> Transform {
+    let direction = ship.rotation * Quat::from_euler(EulerRot::YXZ, orbit.x, orbit.y, 0.0);
+    Transform::from_translation(ship.translation + direction * Vec3::NEG_Z * settings.distance)
+        .looking_at(ship.translation, ship.up())
+}
+
+/// A spot ahead of the ship and off to the side of its path to watch it fly past from.
+fn fly_by_spot(ship: &Transform, velocity: Vec3, settings: &FlyBySettings) -> Vec3 {
+    let direction = velocity.try_normalize().unwrap_or_else(|| *heading(ship));
+    let ahead = (velocity.length() * settings.lead).max(settings.min_distance);
+    ship.translation
+        + direction * ahead
+        + ship.right() * settings.side
+        + ship.up() * settings.height
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+#[autodefault]
+fn on_ship_created_add_camera(
+    mut commands: Commands,
+    settings: Res<CameraSettings>,
+    query: Query<(Entity, &PlayerId, &Controller, &Transform), Added<Controller>>,
+) {
+    for (entity, &player, controller, transform) in query.iter() {
+        // Only players on this machine, or being replayed, need to see through their ship
+        if !matches!(
+            controller,
+            Controller::LocalPlayer | Controller::Replay | Controller::Scripted
+        ) {
+            continue;
+        }
+
+        let pose = chase_goal(transform, &settings.chase);
+        let mut camera = commands.spawn((
+            Camera3dBundle {
+                transform: pose,
+                projection: PerspectiveProjection {
+                    fov: settings.chase.fov,
+                }
+                .into(),
+            },
+            ShipCamera::new(entity, pose),
+            PlayerCamera(player),
+            StateScoped(MainState::InGame),
+        ));
+        // The HUD follows the first player
+        if player == PlayerId(0) {
+            _ = camera.insert(IsDefaultUiCamera);
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn cycle_camera_modes(
+    mut commands: Commands,
+    ships: Query<&ActionState<Action>>,
+    mut cameras: Query<(&mut ShipCamera, &Transform, &Projection)>,
+) {
+    for (mut camera, transform, projection) in &mut cameras {
+        let Ok(action_state) = ships.get(camera.target) else {
+            continue;
+        };
+        if !action_state.just_pressed(&Action::CycleCamera) {
+            continue;
+        }
+
+        let fov = match projection {
+            Projection::Perspective(perspective) => perspective.fov,
+            Projection::Orthographic(_) => FRAC_PI_4,
+        };
+        let mode = camera.mode.next();
+        camera.switch_to(mode, *transform, fov);
+        // The stick orbiting the camera would otherwise steer the ship too
+        let mut ship = commands.entity(camera.target);
+        if mode == CameraMode::Orbit {
+            _ = ship.insert(FreeLook);
+        } else {
+            _ = ship.remove::<FreeLook>();
+        }
+    }
+}
+
+/// Turns orbiting cameras with the devices of the player whose ship they follow.
+#[expect(
+    clippy::too_many_arguments,
+    clippy::needless_pass_by_value,
+    reason = "Bevy System syntax"
+)]
+fn orbit_cameras(
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    devices: Query<&InputDevices>,
+    mut cameras: Query<&mut ShipCamera>,
+) {
+    let motion = mouse_motion.read().map(|motion| motion.delta).sum::<Vec2>();
+    let drag = if mouse_buttons.pressed(MouseButton::Middle) {
+        motion * settings.orbit.mouse_sensitivity
+    } else {
+        Vec2::ZERO
+    };
+
+    for mut camera in &mut cameras {
+        if camera.mode != CameraMode::Orbit {
+            continue;
+        }
+
+        let devices = devices.get(camera.target).ok();
+        let mut turn = Vec2::ZERO;
+        // The mouse belongs to whoever has the keyboard
+        if devices.is_none_or(|devices| devices.keyboard_mouse) {
+            turn += drag;
+        }
+        let assignment = devices.map_or(GamepadAssignment::Any, |devices| devices.gamepad);
+        for gamepad in gamepads.iter().filter(|&gamepad| match assignment {
+            GamepadAssignment::Any => true,
+            GamepadAssignment::Only(assigned) => assigned == gamepad,
+            GamepadAssignment::None => false,
+        }) {
+            let axis = |axis_type| {
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, axis_type))
+                    .unwrap_or_default()
+            };
+            let [x, y] = FREE_LOOK_STICK.map(axis);
+            let stick = Vec2::new(x, -y);
+            if stick.length() > DEADZONE {
+                turn += stick * settings.orbit.stick_speed * time.delta_seconds();
+            }
+        }
+
+        camera.orbit = Vec2::new(
+            camera.orbit.x - turn.x,
+            (camera.orbit.y + turn.y).clamp(-MAX_ORBIT_PITCH, MAX_ORBIT_PITCH),
+        );
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn follow_ships(
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    ships: Query<(&Transform, &LinearVelocity, Option<&CockpitAnchor>), Without<ShipCamera>>,
+    mut cameras: Query<(&mut ShipCamera, &mut Transform, &mut Projection)>,
+) {
+    for (mut camera, mut transform, mut projection) in &mut cameras {
+        let Ok((ship, velocity, anchor)) = ships.get(camera.target) else {
+            continue;
+        };
+        let velocity = velocity.0;
+
+        follow_chase(
+            &mut camera.chase,
+            ship,
+            velocity,
+            time.delta_seconds(),
+            &settings.chase,
+        );
+
+        let (pose, fov) = match camera.mode {
+            CameraMode::Chase => {
+                let speed = (velocity.length() / settings.chase.fov_speed).min(1.0);
+                (
+                    camera.chase.pose,
+                    settings.chase.fov.lerp(settings.chase.max_fov, speed),
+                )
+            }
+            CameraMode::Cockpit => (
+                cockpit_pose(
+                    ship,
+                    anchor.map_or(Vec3::ZERO, |anchor| anchor.0),
+                    &settings.cockpit,
+                ),
+                settings.cockpit.fov,
+            ),
+            CameraMode::Orbit => (
+                orbit_pose(ship, camera.orbit, &settings.orbit),
+                settings.chase.fov,
+            ),
+            CameraMode::Cinematic => {
+                // Once the ship is well past, go and wait for it further along
+                let passed = camera.fly_by.is_none_or(|spot| {
+                    let behind = spot - ship.translation;
+                    behind.dot(velocity) < 0.0 && behind.length() > settings.fly_by.min_distance
+                });
+                if passed {
+                    camera.fly_by = Some(fly_by_spot(ship, velocity, &settings.fly_by));
+                }
+                let spot = camera.fly_by.unwrap_or(ship.translation);
+                (
+                    Transform::from_translation(spot).looking_at(ship.translation, ship.up()),
+                    settings.fly_by.fov,
+                )
+            }
+        };
+
+        let (pose, fov) = match &mut camera.transition {
+            Some(transition) => {
+                transition.elapsed += time.delta_seconds();
+                let progress = (transition.elapsed / settings.transition_duration).min(1.0);
+                // Eases in and out so the camera doesn't lurch off or stop dead
+                let eased = progress * progress * 2.0f32.mul_add(-progress, 3.0);
+                let blended = Transform {
+                    translation: transition.from.translation.lerp(pose.translation, eased),
+                    rotation: transition.from.rotation.slerp(pose.rotation, eased),
+                    ..pose
+                };
+                let fov = transition.from_fov.lerp(fov, eased);
+                if progress >= 1.0 {
+                    camera.transition = None;
+                }
+                (blended, fov)
+            }
+            None => (pose, fov),
+        };
+
+        *transform = pose;
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = fov;
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    inventory_plugin::{CargoContainer, CARGO_CONTAINER_SIZE},
+    states_plugin::MainState,
+};
+
+#[derive(Debug)]
+pub struct CargoPlugin;
+
+impl Plugin for CargoPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(OnEnter(MainState::InGame), setup)
+            .add_systems(
+                Update,
+                on_cargo_container_created_add_visuals.run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct CargoContainerVisuals {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(CargoContainerVisuals {
+        mesh: meshes.add(Cuboid::from_length(CARGO_CONTAINER_SIZE)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.8, 0.6, 0.1),
+            perceptual_roughness: 0.6,
+            ..default()
+        }),
+    });
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn on_cargo_container_created_add_visuals(
+    mut commands: Commands,
+    visuals: Res<CargoContainerVisuals>,
+    query: Query<Entity, Added<CargoContainer>>,
+) {
+    for entity in query.iter() {
+        _ = commands
+            .entity(entity)
+            .insert((visuals.mesh.clone(), visuals.material.clone()));
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    exploration_plugin::{PointOfInterest, PointOfInterestKind, Revealed},
+    states_plugin::MainState,
+};
+
+#[derive(Debug)]
+pub struct ExplorationPlugin;
+
+impl Plugin for ExplorationPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(OnEnter(MainState::InGame), setup)
+            .add_systems(
+                Update,
+                on_point_of_interest_revealed_add_visuals.run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct PointOfInterestVisuals {
+    mesh: Handle<Mesh>,
+    derelict: Handle<StandardMaterial>,
+    anomaly: Handle<StandardMaterial>,
+    beacon: Handle<StandardMaterial>,
+    wreckage: Handle<StandardMaterial>,
+}
+
+impl PointOfInterestVisuals {
+    const fn material(&self, kind: PointOfInterestKind) -> &Handle<StandardMaterial> {
+        match kind {
+            PointOfInterestKind::Derelict => &self.derelict,
+            PointOfInterestKind::Anomaly => &self.anomaly,
+            PointOfInterestKind::Beacon => &self.beacon,
+            PointOfInterestKind::Wreckage => &self.wreckage,
+        }
+    }
+}
+
+const POINT_OF_INTEREST_SIZE: f32 = 8.0;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut glowing = |color: LinearRgba| {
+        materials.add(StandardMaterial {
+            base_color: Color::BLACK,
+            emissive: color * 4.0,
+            ..default()
+        })
+    };
+    commands.insert_resource(PointOfInterestVisuals {
+        mesh: meshes.add(
+            Sphere::new(POINT_OF_INTEREST_SIZE)
+                .mesh()
+                .ico(2)
+                .expect("Icosphere subdivisions are within the supported range"),
+        ),
+        derelict: glowing(LinearRgba::rgb(0.9, 0.6, 0.2)),
+        anomaly: glowing(LinearRgba::rgb(0.7, 0.2, 1.0)),
+        beacon: glowing(LinearRgba::rgb(0.2, 1.0, 0.4)),
+        wreckage: glowing(LinearRgba::rgb(1.0, 0.25, 0.2)),
+    });
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn on_point_of_interest_revealed_add_visuals(
+    mut commands: Commands,
+    visuals: Res<PointOfInterestVisuals>,
+    query: Query<(Entity, &PointOfInterest), Added<Revealed>>,
+) {
+    for (entity, point_of_interest) in query.iter() {
+        _ = commands.entity(entity).insert((
+            visuals.mesh.clone(),
+            visuals.material(point_of_interest.kind).clone(),
+        ));
+    }
+}
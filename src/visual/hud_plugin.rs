@@ -0,0 +1,344 @@
+use autodefault::autodefault;
+use bevy::prelude::*;
+use itertools::Itertools;
+
+use crate::{
+    game::{
+        economy_plugin::{Credits, Market, Station, TradeCompleted, TradeKind, Trader},
+        exploration_plugin::{DiscoveryJournal, PointOfInterestDiscovered},
+        inventory_plugin::{CargoHold, ItemAssets, ItemCatalog},
+        mission_plugin::{
+            MissionAccepted, MissionAssets, MissionCatalog, MissionCompleted, MissionLog,
+            ObjectiveCompleted,
+        },
+        ship_plugin::PlayerId,
+        states_plugin::MainState,
+        targeting_plugin::{
+            CurrentTarget, Destroyed, TargetLockAcquired, TargetLockLost, LOCK_DURATION,
+        },
+    },
+    visual::input_plugin::Controlled,
+};
+
+#[derive(Debug)]
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(OnEnter(MainState::InGame), setup)
+            .add_systems(
+                Update,
+                (
+                    update_target_readout,
+                    update_cargo_readout,
+                    update_market_readout,
+                    update_mission_readout,
+                    log_target_locks,
+                    log_destroyed,
+                    log_trades,
+                    log_missions,
+                    log_discoveries,
+                )
+                    .run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct TargetReadout;
+
+#[derive(Component)]
+struct CargoReadout;
+
+#[derive(Component)]
+struct MarketReadout;
+
+#[derive(Component)]
+struct MissionReadout;
+
+#[autodefault]
+fn setup(mut commands: Commands) {
+    _ = commands.spawn((
+        TextBundle::from_section("", TextStyle { font_size: 20.0 }).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+        }),
+        TargetReadout,
+        StateScoped(MainState::InGame),
+    ));
+    _ = commands.spawn((
+        TextBundle::from_section("", TextStyle { font_size: 20.0 }).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(12.0),
+            left: Val::Px(12.0),
+        }),
+        CargoReadout,
+        StateScoped(MainState::InGame),
+    ));
+    _ = commands.spawn((
+        TextBundle::from_section("", TextStyle { font_size: 20.0 }).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            right: Val::Px(12.0),
+        }),
+        MarketReadout,
+        StateScoped(MainState::InGame),
+    ));
+    _ = commands.spawn((
+        TextBundle::from_section("", TextStyle { font_size: 20.0 }).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(12.0),
+            right: Val::Px(12.0),
+        }),
+        MissionReadout,
+        StateScoped(MainState::InGame),
+    ));
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_target_readout(
+    ships: Query<(&Transform, &CurrentTarget, &PlayerId), With<Controlled>>,
+    targets: Query<&Transform>,
+    mut readouts: Query<&mut Text, With<TargetReadout>>,
+) {
+    let readout = ships
+        .iter()
+        .min_by_key(|(.., player)| **player)
+        .and_then(|(transform, current_target, _)| {
+            let target = current_target.entity?;
+            let distance = transform
+                .translation
+                .distance(targets.get(target).ok()?.translation);
+            let lock = if current_target.locked_entity().is_some() {
+                "LOCKED".to_owned()
+            } else {
+                format!(
+                    "locking {:.0}%",
+                    (current_target.lock_progress / LOCK_DURATION).min(1.0) * 100.0
+                )
+            };
+            Some(format!("Target {target} {distance:.0}m {lock}"))
+        })
+        .unwrap_or_else(|| "No target".to_owned());
+
+    for mut text in &mut readouts {
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&readout);
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_cargo_readout(
+    item_assets: Res<ItemAssets>,
+    catalogs: Res<Assets<ItemCatalog>>,
+    ships: Query<(Ref<CargoHold>, &PlayerId), With<Controlled>>,
+    mut readouts: Query<&mut Text, With<CargoReadout>>,
+) {
+    let (Some((cargo_hold, _)), Some(catalog)) = (
+        ships.iter().min_by_key(|(_, player)| **player),
+        item_assets.catalog(&catalogs),
+    ) else {
+        return;
+    };
+    if !cargo_hold.is_changed() {
+        return;
+    }
+
+    let items = cargo_hold
+        .items()
+        .map(|(item, quantity)| {
+            let name = catalog
+                .get(item)
+                .map_or_else(|_| item.to_string(), |definition| definition.name.clone());
+            format!("\n  {quantity} x {name}")
+        })
+        .join("");
+    let readout = format!(
+        "Cargo {:.0}/{:.0} m3 {:.1}/{:.0} t{items}",
+        cargo_hold.volume(),
+        cargo_hold.volume_capacity,
+        cargo_hold.mass(),
+        cargo_hold.mass_capacity
+    );
+
+    for mut text in &mut readouts {
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&readout);
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_market_readout(
+    item_assets: Res<ItemAssets>,
+    catalogs: Res<Assets<ItemCatalog>>,
+    ships: Query<(&Credits, &Trader, &PlayerId), With<Controlled>>,
+    stations: Query<(&Station, &Market)>,
+    mut readouts: Query<&mut Text, With<MarketReadout>>,
+) {
+    let Some((credits, trader, _)) = ships.iter().min_by_key(|(.., player)| **player) else {
+        return;
+    };
+
+    let mut readout = format!("Credits {}", credits.0);
+    if let Some((station, market)) = trader
+        .station
+        .and_then(|station| stations.get(station).ok())
+    {
+        let catalog = item_assets.catalog(&catalogs);
+        let commodities = market
+            .commodities()
+            .enumerate()
+            .map(|(index, (item, commodity))| {
+                let marker = if index == trader.selected { ">" } else { " " };
+                let name = catalog
+                    .and_then(|catalog| catalog.get(item).ok())
+                    .map_or_else(|| item.to_string(), |definition| definition.name.clone());
+                format!(
+                    "\n{marker} {name}: buy {} sell {} ({:.0} in stock)",
+                    commodity.buy_price(),
+                    commodity.sell_price(),
+                    commodity.supply()
+                )
+            })
+            .join("");
+        readout = format!("{}\n{readout}{commodities}", station.name);
+    }
+
+    for mut text in &mut readouts {
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&readout);
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_mission_readout(
+    mission_assets: Res<MissionAssets>,
+    catalogs: Res<Assets<MissionCatalog>>,
+    mission_log: Res<MissionLog>,
+    journal: Res<DiscoveryJournal>,
+    ships: Query<(Entity, &PlayerId), With<Controlled>>,
+    mut readouts: Query<&mut Text, With<MissionReadout>>,
+) {
+    let (Some((ship, &player)), Some(catalog)) = (
+        ships.iter().min_by_key(|(_, player)| **player),
+        mission_assets.catalog(&catalogs),
+    ) else {
+        return;
+    };
+
+    let missions = mission_log
+        .active
+        .iter()
+        .filter(|active| active.ship == ship)
+        .filter_map(|active| {
+            let definition = catalog.missions.get(&active.mission)?;
+            let objectives = definition
+                .objectives
+                .iter()
+                .zip(&active.progress)
+                .map(|(objective, progress)| {
+                    format!("\n  {objective} ({progress}/{})", objective.required())
+                })
+                .join("");
+            Some(format!("\n{}{objectives}", definition.title))
+        })
+        .join("");
+    let (found, first) =
+        journal
+            .discovered_by(player)
+            .fold((0, 0), |(found, first), (_, discovery)| {
+                (
+                    found + 1,
+                    first + usize::from(discovery.first_discovered_by == player),
+                )
+            });
+    let readout = format!(
+        "Missions{missions}\nJournal {found}/{} discovered, {first} first",
+        journal.discoveries().count()
+    );
+
+    for mut text in &mut readouts {
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&readout);
+        }
+    }
+}
+
+fn log_missions(
+    mut missions_accepted: EventReader<MissionAccepted>,
+    mut objectives_completed: EventReader<ObjectiveCompleted>,
+    mut missions_completed: EventReader<MissionCompleted>,
+) {
+    for event in missions_accepted.read() {
+        info!("{:?} accepted mission {}", event.ship, event.mission);
+    }
+    for event in objectives_completed.read() {
+        info!(
+            "{:?} completed objective {} of mission {}",
+            event.ship, event.objective, event.mission
+        );
+    }
+    for event in missions_completed.read() {
+        info!("{:?} completed mission {}", event.ship, event.mission);
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn log_discoveries(mut discovered: EventReader<PointOfInterestDiscovered>, names: Query<&Name>) {
+    for event in discovered.read() {
+        let name = names.get(event.point_of_interest).map_or_else(
+            |_| format!("{:?}", event.point_of_interest),
+            ToString::to_string,
+        );
+        let first = if event.first { " first" } else { "" };
+        info!(
+            "{} ({:?}){first} discovered {name}",
+            event.player, event.ship
+        );
+    }
+}
+
+fn log_trades(mut trades_completed: EventReader<TradeCompleted>) {
+    for trade in trades_completed.read() {
+        let request = &trade.request;
+        let verb = match request.kind {
+            TradeKind::Buy => "bought",
+            TradeKind::Sell => "sold",
+        };
+        info!(
+            "{:?} {verb} {} {} for {} credits",
+            request.ship, request.quantity, request.item, trade.credits
+        );
+    }
+}
+
+fn log_destroyed(mut destroyed: EventReader<Destroyed>) {
+    for event in destroyed.read() {
+        let name = event
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("{:?}", event.entity));
+        if let Some(attacker) = event.attacker {
+            info!("{name} destroyed by {attacker:?}");
+        } else {
+            info!("{name} destroyed");
+        }
+    }
+}
+
+fn log_target_locks(
+    mut lock_acquired: EventReader<TargetLockAcquired>,
+    mut lock_lost: EventReader<TargetLockLost>,
+) {
+    for event in lock_acquired.read() {
+        info!("{:?} locked on to {:?}", event.ship, event.target);
+    }
+    for event in lock_lost.read() {
+        info!("{:?} lost lock on {:?}", event.ship, event.target);
+    }
+}
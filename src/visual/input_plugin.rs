@@ -1,11 +1,35 @@
+use core::error::Error;
+use std::{fs, io, path::Path};
+
 use autodefault::autodefault;
-use bevy::{prelude::*, utils::HashMap};
+use derive_more::Display;
+use ron::{
+    error::SpannedError,
+    ser::{to_string_pretty, PrettyConfig},
+};
+use serde::{Deserialize, Serialize};
+
+use bevy::{
+    input::{
+        gamepad::{ButtonSettings, GamepadConnection, GamepadConnectionEvent, GamepadSettings},
+        mouse::MouseMotion,
+    },
+    prelude::*,
+    utils::HashMap,
+    window::{CursorGrabMode, PrimaryWindow},
+};
 
-use leafwing_input_manager::{buttonlike::ButtonState, prelude::*};
+use leafwing_input_manager::{
+    axislike::{AxisType, VirtualAxis},
+    buttonlike::ButtonState,
+    prelude::*,
+    user_input::InputKind,
+};
 
 use crate::game::{
-    ship_plugin::{ActionEventData, Ship},
-    states_plugin::{FrameSystemsSet, InGameState, MainState},
+    save_plugin::{SaveRequested, QUICKSAVE_PATH},
+    ship_plugin::{ActionEventData, Controller, PlayerId},
+    states_plugin::{reset_resource, FrameSystemsSet, InGameState, MainState},
 };
 
 #[derive(Debug)]
@@ -15,18 +39,51 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         _ = app
             .add_plugins(InputManagerPlugin::<Action>::default())
+            .init_resource::<MouseFlight>()
+            .init_resource::<ActionState<Action>>()
+            .add_systems(Startup, load_bindings)
             .add_systems(
-                FixedUpdate,
-                (on_ship_created_add_input, process_inputs)
-                    .in_set(FrameSystemsSet::Input)
+                Update,
+                (
+                    apply_bindings.run_if(resource_exists_and_changed::<InputBindings>),
+                    assign_input_devices.run_if(in_state(MainState::InGame)),
+                    apply_free_look.run_if(in_state(MainState::InGame)),
+                    grab_cursor,
+                    use_analog_triggers,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_mouse_flight,
+                    steer_mouse_flight,
+                    toggle_throttle_mode,
+                )
+                    .chain()
                     .run_if(in_state(MainState::InGame))
                     .run_if(in_state(InGameState::Running)),
+            )
+            .add_systems(OnExit(MainState::InGame), reset_resource::<MouseFlight>)
+            .add_systems(
+                FixedUpdate,
+                (
+                    on_ship_created_add_input,
+                    // The player is in the pause menu, not flying
+                    process_inputs.run_if(in_state(InGameState::Running)),
+                )
+                    .in_set(FrameSystemsSet::Input)
+                    .run_if(in_state(MainState::InGame)),
+            )
+            .add_systems(OnEnter(InGameState::Paused), release_controls)
+            .add_systems(
+                Update,
+                request_quicksave.run_if(in_state(MainState::InGame)),
             );
     }
 }
 
-#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
-enum Action {
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect, Serialize, Deserialize)]
+pub enum Action {
     ForwardThrust,
     ReverseThrust,
     Aileron,  // Roll
@@ -35,9 +92,74 @@ enum Action {
     Action1,
     Action2,
     AutoBalance,
+    FormUp,
+    BreakFormation,
+    AttackMyTarget,
+    TargetNearest,
+    TargetNext,
+    TargetPrevious,
+    Jettison,
+    MiningLaser,
+    Scan,
+    MarketNext,
+    MarketBuy,
+    MarketSell,
+    MouseFlight,
+    ThrottleMode,
+    CycleCamera,
+    Pause,
+    Quicksave,
+}
+
+impl Action {
+    pub const ALL: [Self; 25] = [
+        Self::ForwardThrust,
+        Self::ReverseThrust,
+        Self::Aileron,
+        Self::Elevator,
+        Self::Rudder,
+        Self::Action1,
+        Self::Action2,
+        Self::AutoBalance,
+        Self::FormUp,
+        Self::BreakFormation,
+        Self::AttackMyTarget,
+        Self::TargetNearest,
+        Self::TargetNext,
+        Self::TargetPrevious,
+        Self::Jettison,
+        Self::MiningLaser,
+        Self::Scan,
+        Self::MarketNext,
+        Self::MarketBuy,
+        Self::MarketSell,
+        Self::MouseFlight,
+        Self::ThrottleMode,
+        Self::CycleCamera,
+        Self::Pause,
+        Self::Quicksave,
+    ];
+
+    /// Actions driven by an axis, which take a pair of keys when bound to the keyboard.
+    pub const fn is_axis(self) -> bool {
+        matches!(self, Self::Aileron | Self::Elevator | Self::Rudder)
+    }
+
+    /// Actions about the game rather than a ship, read from the [`ActionState`] resource so they
+    /// work without a ship to fly, e.g. during a replay.
+    pub const fn is_global(self) -> bool {
+        matches!(self, Self::Pause | Self::Quicksave)
+    }
 }
 
-const DEADZONE: f32 = 0.1;
+/// The default inner deadzone of the [`AxisResponse`]s, which are the only deadzone sticks get.
+pub const DEADZONE: f32 = 0.1;
+
+/// Held for the less used gamepad actions, there aren't enough buttons to give each its own.
+const GAMEPAD_MODIFIER: GamepadButtonType = GamepadButtonType::LeftTrigger;
+
+/// Where the player's bindings are kept between runs.
+pub const INPUT_CONFIG_PATH: &str = "config/input.ron";
 
 fn default_input_map() -> InputMap<Action> {
     let input_map = InputMap::default()
@@ -53,28 +175,762 @@ fn default_input_map() -> InputMap<Action> {
         .insert(Action::Action1, MouseButton::Right)
         .insert(Action::Action2, MouseButton::Left)
         .insert(Action::AutoBalance, KeyCode::KeyB)
+        .insert(Action::FormUp, KeyCode::F1)
+        .insert(Action::BreakFormation, KeyCode::F2)
+        .insert(Action::AttackMyTarget, KeyCode::F3)
+        .insert(Action::TargetNearest, KeyCode::KeyT)
+        .insert(Action::TargetNext, KeyCode::BracketRight)
+        .insert(Action::TargetPrevious, KeyCode::BracketLeft)
+        .insert(Action::Jettison, KeyCode::KeyJ)
+        .insert(Action::MiningLaser, KeyCode::Space)
+        .insert(Action::Scan, KeyCode::KeyR)
+        .insert(Action::MarketNext, KeyCode::Tab)
+        .insert(Action::MarketBuy, KeyCode::Equal)
+        .insert(Action::MarketSell, KeyCode::Minus)
+        .insert(Action::MouseFlight, KeyCode::KeyM)
+        .insert(Action::ThrottleMode, KeyCode::KeyL)
+        .insert(Action::CycleCamera, KeyCode::KeyC)
+        .insert(Action::Pause, KeyCode::Escape)
+        .insert(Action::Quicksave, KeyCode::F5)
         // Gamepad
         .insert(Action::ForwardThrust, GamepadButtonType::RightTrigger2)
         .insert(Action::ReverseThrust, GamepadButtonType::LeftTrigger2)
         .insert(
             Action::Aileron,
-            SingleAxis::symmetric(GamepadAxisType::LeftStickX, DEADZONE),
+            SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0),
         )
         .insert(
             Action::Elevator,
-            SingleAxis::symmetric(GamepadAxisType::LeftStickY, DEADZONE),
+            SingleAxis::symmetric(GamepadAxisType::LeftStickY, 0.0),
         )
         .insert(
             Action::Rudder,
-            SingleAxis::symmetric(GamepadAxisType::RightStickX, DEADZONE),
+            SingleAxis::symmetric(GamepadAxisType::RightStickX, 0.0),
         )
         .insert(Action::Action1, GamepadButtonType::RightTrigger)
         .insert(Action::Action2, GamepadButtonType::LeftTrigger)
+        .insert(
+            Action::AutoBalance,
+            UserInput::chord([GAMEPAD_MODIFIER, GamepadButtonType::South]),
+        )
+        .insert(Action::FormUp, GamepadButtonType::DPadUp)
+        .insert(Action::BreakFormation, GamepadButtonType::DPadDown)
+        .insert(Action::AttackMyTarget, GamepadButtonType::DPadRight)
+        .insert(Action::TargetNearest, GamepadButtonType::North)
+        .insert(Action::TargetNext, GamepadButtonType::East)
+        .insert(Action::TargetPrevious, GamepadButtonType::West)
+        .insert(Action::Jettison, GamepadButtonType::Select)
+        .insert(Action::MiningLaser, GamepadButtonType::South)
+        .insert(Action::Scan, GamepadButtonType::Mode)
+        .insert(Action::MarketNext, GamepadButtonType::DPadLeft)
+        .insert(
+            Action::MarketBuy,
+            UserInput::chord([GAMEPAD_MODIFIER, GamepadButtonType::DPadLeft]),
+        )
+        .insert(Action::MarketSell, GamepadButtonType::LeftThumb)
+        .insert(
+            Action::ThrottleMode,
+            UserInput::chord([GAMEPAD_MODIFIER, GamepadButtonType::North]),
+        )
+        .insert(Action::CycleCamera, GamepadButtonType::RightThumb)
+        .insert(Action::Pause, GamepadButtonType::Start)
+        .insert(
+            Action::Quicksave,
+            UserInput::chord([GAMEPAD_MODIFIER, GamepadButtonType::Start]),
+        )
         .build();
 
     input_map
 }
 
+#[derive(Debug, Display)]
+pub enum BindingsError {
+    #[display("Could not access input config: {_0}")]
+    Io(io::Error),
+    #[display("Could not write input config: {_0}")]
+    Serialize(ron::Error),
+    #[display("Could not read input config: {_0}")]
+    Parse(SpannedError),
+}
+
+impl Error for BindingsError {}
+
+/// The player's input bindings and axis tuning, shared by every ship they control.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+pub struct InputBindings {
+    pub map: InputMap<Action>,
+    #[serde(default)]
+    pub axes: AxisResponses,
+    #[serde(default)]
+    pub mouse_flight: MouseFlightSettings,
+    #[serde(default)]
+    pub throttle: ThrottleMode,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            map: default_input_map(),
+            axes: AxisResponses::default(),
+            mouse_flight: MouseFlightSettings::default(),
+            throttle: ThrottleMode::default(),
+        }
+    }
+}
+
+impl InputBindings {
+    pub fn read(path: &Path) -> Result<Self, BindingsError> {
+        let ron = fs::read_to_string(path).map_err(BindingsError::Io)?;
+        let mut bindings: Self = ron::from_str(&ron).map_err(BindingsError::Parse)?;
+        bindings.bind_new_actions();
+        bindings.clear_axis_thresholds();
+        Ok(bindings)
+    }
+
+    /// Lets the whole stick travel through to the [`AxisResponse`]s, older bindings were saved
+    /// with a deadzone of their own on top.
+    fn clear_axis_thresholds(&mut self) {
+        for action in Action::ALL {
+            for input in self.map.get_mut(&action).into_iter().flatten() {
+                if let UserInput::Single(InputKind::SingleAxis(axis)) = input {
+                    axis.positive_low = 0.0;
+                    axis.negative_low = 0.0;
+                }
+            }
+        }
+    }
+
+    /// Gives actions added since the bindings were saved their default bindings.
+    fn bind_new_actions(&mut self) {
+        let defaults = default_input_map();
+        for action in Action::ALL {
+            if self.map.get(&action).is_none() {
+                for input in defaults.get(&action).into_iter().flatten() {
+                    _ = self.map.insert(action, input.clone());
+                }
+            }
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), BindingsError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(BindingsError::Io)?;
+        }
+        let ron =
+            to_string_pretty(self, PrettyConfig::default()).map_err(BindingsError::Serialize)?;
+        fs::write(path, ron).map_err(BindingsError::Io)
+    }
+
+    /// Replaces the action's binding on the same kind of device, keyboard and mouse or gamepad.
+    pub fn rebind(&mut self, action: Action, input: UserInput) {
+        let gamepad = is_gamepad_input(&input);
+        if let Some(inputs) = self.map.get_mut(&action) {
+            inputs.retain(|existing| is_gamepad_input(existing) != gamepad);
+        }
+        _ = self.map.insert(action, input);
+    }
+
+    /// Inputs bound to more than one action, with the actions they are bound to.
+    pub fn conflicts(&self) -> Vec<(Vec<InputKind>, Vec<Action>)> {
+        let mut conflicts = Vec::<(Vec<InputKind>, Vec<Action>)>::new();
+        for action in Action::ALL {
+            let inputs = self.map.get(&action).into_iter().flatten();
+            for kinds in inputs.flat_map(clashing_inputs) {
+                match conflicts
+                    .iter_mut()
+                    .find(|(existing, _)| same_inputs(existing, &kinds))
+                {
+                    Some((_, actions)) if !actions.contains(&action) => actions.push(action),
+                    Some(_) => {}
+                    None => conflicts.push((kinds, vec![action])),
+                }
+            }
+        }
+        conflicts.retain(|(_, actions)| actions.len() > 1);
+        conflicts
+    }
+}
+
+/// How raw axis values turn into control values, one per axis action.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AxisResponses {
+    pub roll: AxisResponse,
+    pub pitch: AxisResponse,
+    pub yaw: AxisResponse,
+}
+
+impl AxisResponses {
+    pub const fn get(&self, action: Action) -> Option<&AxisResponse> {
+        match action {
+            Action::Aileron => Some(&self.roll),
+            Action::Elevator => Some(&self.pitch),
+            Action::Rudder => Some(&self.yaw),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct AxisResponse {
+    /// Values up to this far from the center read as zero, e.g. to hide stick drift.
+    pub inner_deadzone: f32,
+    /// Values from this far out read as fully deflected, e.g. for sticks that don't reach the edge.
+    pub outer_deadzone: f32,
+    pub curve: ResponseCurve,
+    pub inverted: bool,
+    /// Scales the shaped value, the result is still clamped to full deflection.
+    pub sensitivity: f32,
+}
+
+impl Default for AxisResponse {
+    fn default() -> Self {
+        Self {
+            inner_deadzone: DEADZONE,
+            outer_deadzone: 1.0,
+            curve: ResponseCurve::Linear,
+            inverted: false,
+            sensitivity: 1.0,
+        }
+    }
+}
+
+impl AxisResponse {
+    /// Turns a raw axis value in `[-1, 1]` into a control value in `[-1, 1]`.
+    pub fn apply(&self, value: f32) -> f32 {
+        let magnitude = value.abs();
+        if magnitude <= self.inner_deadzone {
+            return 0.0;
+        }
+
+        let range = (self.outer_deadzone - self.inner_deadzone).max(f32::EPSILON);
+        let normalized = ((magnitude - self.inner_deadzone) / range).min(1.0);
+        let shaped = self.curve.evaluate(normalized).copysign(value);
+        let direction = if self.inverted { -1.0 } else { 1.0 };
+        (shaped * direction * self.sensitivity).clamp(-1.0, 1.0)
+    }
+}
+
+/// Shapes an axis deflection in `[0, 1]`, keeping the ends in place.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    /// Raises the deflection to this power, above 1 gives finer control around the center.
+    Exponential(f32),
+    /// Straight lines between `(deflection, value)` points, which should be sorted by deflection.
+    Custom(Vec<(f32, f32)>),
+}
+
+impl ResponseCurve {
+    pub fn evaluate(&self, deflection: f32) -> f32 {
+        let deflection = deflection.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => deflection,
+            Self::Exponential(exponent) => deflection.powf(exponent.max(f32::EPSILON)),
+            Self::Custom(points) => {
+                // The curve always runs from the center to full deflection
+                let start = [(0.0, 0.0)];
+                let end = [(1.0, 1.0)];
+                let points = start.iter().chain(points).chain(&end);
+                points
+                    .clone()
+                    .zip(points.skip(1))
+                    .find(|(_, (to_x, _))| deflection <= *to_x)
+                    .map_or(deflection, |(&(from_x, from_y), &(to_x, to_y))| {
+                        if to_x - from_x <= f32::EPSILON {
+                            to_y
+                        } else {
+                            from_y + (to_y - from_y) * (deflection - from_x) / (to_x - from_x)
+                        }
+                    })
+            }
+        }
+    }
+}
+
+/// How the thrust inputs drive the ship's thrust.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThrottleMode {
+    /// Thrust follows the inputs, letting go cuts the engines.
+    #[default]
+    Direct,
+    /// The inputs move a throttle lever that stays where it was left.
+    Lever,
+}
+
+/// How far a fully pressed thrust input moves the throttle lever each second.
+const THROTTLE_LEVER_RATE: f32 = 0.5;
+
+/// Trigger travel that counts as pressed, low so the whole travel drives thrust.
+const TRIGGER_PRESS_THRESHOLD: f32 = 0.02;
+const TRIGGER_RELEASE_THRESHOLD: f32 = 0.01;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct MouseFlightSettings {
+    /// How far the mouse moves, in pixels, to fully deflect the virtual stick.
+    pub radius: f32,
+    /// Fraction of the radius around the center that reads as no input.
+    pub deadzone: f32,
+    /// Fraction of the way back to the center the stick drifts each second, 0 leaves it where it is.
+    pub recenter_rate: f32,
+    pub invert_pitch: bool,
+}
+
+impl Default for MouseFlightSettings {
+    fn default() -> Self {
+        Self {
+            radius: 200.0,
+            deadzone: 0.05,
+            recenter_rate: 1.0,
+            invert_pitch: false,
+        }
+    }
+}
+
+/// Mouse flight turns mouse movement into a virtual stick for pitch and yaw.
+#[derive(Resource, Debug, Default)]
+pub struct MouseFlight {
+    pub active: bool,
+    /// How far the virtual stick has been pushed, in pixels, screen down being positive y.
+    offset: Vec2,
+}
+
+impl MouseFlight {
+    /// Yaw and pitch from the virtual stick, each in `[-1, 1]`.
+    pub fn stick(&self, settings: &MouseFlightSettings) -> Vec2 {
+        if !self.active {
+            return Vec2::ZERO;
+        }
+
+        let deflection = (self.offset / settings.radius.max(1.0)).clamp_length_max(1.0);
+        let magnitude = deflection.length();
+        if magnitude <= settings.deadzone {
+            return Vec2::ZERO;
+        }
+
+        // Rescaled so the stick leaves the deadzone circle smoothly rather than jumping
+        let scaled = (magnitude - settings.deadzone) / (1.0 - settings.deadzone).max(f32::EPSILON);
+        let stick = deflection / magnitude * scaled;
+        // Pushing the mouse away pitches the nose up, unless inverted like a flight stick
+        let pitch = if settings.invert_pitch {
+            -stick.y
+        } else {
+            stick.y
+        };
+        Vec2::new(stick.x, pitch)
+    }
+}
+
+/// The individual buttons and axes making up an input.
+fn input_kinds(input: &UserInput) -> Vec<InputKind> {
+    match input {
+        UserInput::Single(kind) => vec![*kind],
+        UserInput::Chord(kinds) => kinds.clone(),
+        UserInput::VirtualDPad(dpad) => vec![dpad.up, dpad.down, dpad.left, dpad.right],
+        UserInput::VirtualAxis(axis) => vec![axis.negative, axis.positive],
+    }
+}
+
+/// The buttons and axes of an input that can't also be bound to something else, a chord takes
+/// priority over its buttons on their own so it only clashes with the same chord.
+fn clashing_inputs(input: &UserInput) -> Vec<Vec<InputKind>> {
+    match input {
+        UserInput::Chord(kinds) => vec![kinds.clone()],
+        _ => input_kinds(input)
+            .into_iter()
+            .map(|kind| vec![kind])
+            .collect(),
+    }
+}
+
+/// Whether two inputs read the same button or axis, regardless of how the axis is tuned.
+fn same_input(a: &InputKind, b: &InputKind) -> bool {
+    match (a, b) {
+        (InputKind::SingleAxis(a), InputKind::SingleAxis(b)) => a.axis_type == b.axis_type,
+        _ => a == b,
+    }
+}
+
+fn same_inputs(a: &[InputKind], b: &[InputKind]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_input(a, b))
+}
+
+/// The right stick, which turns the camera instead of the ship while the player has [`FreeLook`].
+pub const FREE_LOOK_STICK: [GamepadAxisType; 2] =
+    [GamepadAxisType::RightStickX, GamepadAxisType::RightStickY];
+
+/// Hands a player's [`FREE_LOOK_STICK`] over from flying their ship to looking around it.
+#[derive(Component, Debug)]
+pub struct FreeLook;
+
+fn is_free_look_axis(axis: &SingleAxis) -> bool {
+    matches!(axis.axis_type, AxisType::Gamepad(axis_type) if FREE_LOOK_STICK.contains(&axis_type))
+}
+
+/// Unbinds the [`FREE_LOOK_STICK`] from every action, so looking around doesn't also steer.
+fn hand_over_free_look_stick(input_map: &mut InputMap<Action>) {
+    for action in Action::ALL {
+        if let Some(inputs) = input_map.get_mut(&action) {
+            inputs.retain(|input| {
+                !input_kinds(input).iter().any(|kind| match kind {
+                    InputKind::SingleAxis(axis) => is_free_look_axis(axis),
+                    InputKind::DualAxis(axis) => {
+                        is_free_look_axis(&axis.x) || is_free_look_axis(&axis.y)
+                    }
+                    _ => false,
+                })
+            });
+        }
+    }
+}
+
+fn is_gamepad_input(input: &UserInput) -> bool {
+    input_kinds(input).iter().any(|kind| match kind {
+        InputKind::GamepadButton(_) => true,
+        InputKind::SingleAxis(axis) => matches!(axis.axis_type, AxisType::Gamepad(_)),
+        InputKind::DualAxis(axis) => matches!(axis.x.axis_type, AxisType::Gamepad(_)),
+        _ => false,
+    })
+}
+
+/// A short name for an input, for showing next to the action it's bound to.
+pub fn input_label(input: &UserInput) -> String {
+    match input {
+        UserInput::VirtualAxis(VirtualAxis { negative, positive }) => {
+            format!("{} / {}", kind_label(negative), kind_label(positive))
+        }
+        _ => input_kinds(input)
+            .iter()
+            .map(kind_label)
+            .collect::<Vec<_>>()
+            .join(" + "),
+    }
+}
+
+pub fn kind_label(kind: &InputKind) -> String {
+    match kind {
+        InputKind::SingleAxis(axis) => match axis.axis_type {
+            AxisType::Gamepad(axis) => format!("{axis:?}"),
+            AxisType::MouseWheel(axis) => format!("Wheel {axis:?}"),
+            AxisType::MouseMotion(axis) => format!("Mouse {axis:?}"),
+        },
+        _ => kind.to_string(),
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn toggle_mouse_flight(
+    query: Query<&ActionState<Action>, With<Controlled>>,
+    mut mouse_flight: ResMut<MouseFlight>,
+) {
+    if query
+        .iter()
+        .any(|action_state| action_state.just_pressed(&Action::MouseFlight))
+    {
+        *mouse_flight = MouseFlight {
+            active: !mouse_flight.active,
+            offset: Vec2::ZERO,
+        };
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn steer_mouse_flight(
+    time: Res<Time>,
+    bindings: Res<InputBindings>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_flight: ResMut<MouseFlight>,
+) {
+    let motion = mouse_motion.read().map(|motion| motion.delta).sum::<Vec2>();
+    if !mouse_flight.active {
+        return;
+    }
+
+    let settings = &bindings.mouse_flight;
+    let recentered = mouse_flight.offset
+        * settings
+            .recenter_rate
+            .mul_add(-time.delta_seconds(), 1.0)
+            .clamp(0.0, 1.0);
+    mouse_flight.offset = (recentered + motion).clamp_length_max(settings.radius);
+}
+
+/// Keeps the cursor locked to the window while the mouse is flying the ship.
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn grab_cursor(
+    mouse_flight: Res<MouseFlight>,
+    main_state: Res<State<MainState>>,
+    in_game_state: Res<State<InGameState>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let grabbed = mouse_flight.active
+        && *main_state.get() == MainState::InGame
+        && *in_game_state.get() == InGameState::Running;
+    for mut window in &mut windows {
+        if window.cursor.visible == grabbed {
+            window.cursor.visible = !grabbed;
+            window.cursor.grab_mode = if grabbed {
+                CursorGrabMode::Locked
+            } else {
+                CursorGrabMode::None
+            };
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn toggle_throttle_mode(
+    actions: Query<&ActionState<Action>, With<Controlled>>,
+    mut controlled: Query<&mut Controlled>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    if !actions
+        .iter()
+        .any(|action_state| action_state.just_pressed(&Action::ThrottleMode))
+    {
+        return;
+    }
+
+    bindings.throttle = match bindings.throttle {
+        ThrottleMode::Direct => ThrottleMode::Lever,
+        ThrottleMode::Lever => ThrottleMode::Direct,
+    };
+    for mut controlled in &mut controlled {
+        controlled.throttle_lever = 0.0;
+    }
+}
+
+/// Bevy only reports triggers as pressed past three quarters of their travel, which would leave
+/// thrust with just the last bit of it.
+fn use_analog_triggers(
+    mut connections: EventReader<GamepadConnectionEvent>,
+    mut gamepad_settings: ResMut<GamepadSettings>,
+) {
+    for connection in connections.read() {
+        if !matches!(connection.connection, GamepadConnection::Connected(_)) {
+            continue;
+        }
+
+        for button_type in [
+            GamepadButtonType::LeftTrigger2,
+            GamepadButtonType::RightTrigger2,
+        ] {
+            _ = gamepad_settings.button_settings.insert(
+                GamepadButton::new(connection.gamepad, button_type),
+                ButtonSettings::new(TRIGGER_PRESS_THRESHOLD, TRIGGER_RELEASE_THRESHOLD)
+                    .expect("Trigger thresholds are valid"),
+            );
+        }
+    }
+}
+
+fn load_bindings(mut commands: Commands) {
+    let path = Path::new(INPUT_CONFIG_PATH);
+    let bindings = if path.exists() {
+        InputBindings::read(path).unwrap_or_else(|error| {
+            error!("Using default bindings: {error}");
+            InputBindings::default()
+        })
+    } else {
+        InputBindings::default()
+    };
+    commands.insert_resource(bindings);
+}
+
+/// Which gamepad a local player's ship listens to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadAssignment {
+    /// Every gamepad, when there is nobody else to share them with.
+    Any,
+    Only(Gamepad),
+    None,
+}
+
+/// The devices a local player flies their ship with.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputDevices {
+    pub keyboard_mouse: bool,
+    pub gamepad: GamepadAssignment,
+}
+
+impl InputDevices {
+    /// The bindings for just these devices, so players sharing a screen don't fly each other.
+    fn input_map(self, bindings: &InputMap<Action>) -> InputMap<Action> {
+        let mut input_map = InputMap::default();
+        for (&action, inputs) in bindings.iter() {
+            for input in inputs {
+                let wanted = if is_gamepad_input(input) {
+                    self.gamepad != GamepadAssignment::None
+                } else {
+                    self.keyboard_mouse
+                };
+                if wanted {
+                    _ = input_map.insert(action, input.clone());
+                }
+            }
+        }
+        if let GamepadAssignment::Only(gamepad) = self.gamepad {
+            _ = input_map.set_gamepad(gamepad);
+        }
+        input_map
+    }
+}
+
+/// Spreads the keyboard and connected gamepads over the local players, the first player always
+/// has the keyboard, and a gamepad too when there are enough for everyone.
+fn input_devices(local_players: usize, gamepads: &[Gamepad]) -> Vec<InputDevices> {
+    if local_players <= 1 {
+        return vec![InputDevices {
+            keyboard_mouse: true,
+            gamepad: GamepadAssignment::Any,
+        }];
+    }
+
+    let keyboard_player_has_gamepad = gamepads.len() >= local_players;
+    let mut gamepads = gamepads.iter().copied();
+    (0..local_players)
+        .map(|player| {
+            let gamepad = if player > 0 || keyboard_player_has_gamepad {
+                gamepads.next()
+            } else {
+                None
+            };
+            InputDevices {
+                keyboard_mouse: player == 0,
+                gamepad: gamepad.map_or(GamepadAssignment::None, GamepadAssignment::Only),
+            }
+        })
+        .collect()
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+#[expect(clippy::type_complexity, reason = "Bevy Query syntax")]
+fn assign_input_devices(
+    mut commands: Commands,
+    bindings: Res<InputBindings>,
+    gamepads: Res<Gamepads>,
+    added: Query<(), Added<Controlled>>,
+    mut ships: Query<
+        (
+            Entity,
+            &PlayerId,
+            Option<&InputDevices>,
+            &mut InputMap<Action>,
+            Has<FreeLook>,
+        ),
+        With<Controlled>,
+    >,
+) {
+    if !gamepads.is_changed() && added.is_empty() {
+        return;
+    }
+
+    let mut connected = gamepads.iter().collect::<Vec<_>>();
+    connected.sort_by_key(|gamepad| gamepad.id);
+    let mut ships = ships.iter_mut().collect::<Vec<_>>();
+    ships.sort_by_key(|(_, player, ..)| **player);
+
+    let assignments = input_devices(ships.len(), &connected);
+    for ((entity, player, current, mut input_map, free_look), devices) in
+        ships.into_iter().zip(assignments)
+    {
+        if current == Some(&devices) {
+            continue;
+        }
+        if !devices.keyboard_mouse && devices.gamepad == GamepadAssignment::None {
+            warn!("{player} has no gamepad to fly with");
+        }
+        *input_map = devices.input_map(&bindings.map);
+        report_unmapped_actions(*player, &input_map, devices.keyboard_mouse);
+        if free_look {
+            hand_over_free_look_stick(&mut input_map);
+        }
+        _ = commands.entity(entity).insert(devices);
+    }
+}
+
+/// Hands changed bindings to the controlled ships and the global actions, and stores them for
+/// next time.
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+#[expect(clippy::type_complexity, reason = "Bevy Query syntax")]
+fn apply_bindings(
+    mut commands: Commands,
+    bindings: Res<InputBindings>,
+    mut input_maps: Query<
+        (
+            &mut InputMap<Action>,
+            &PlayerId,
+            Option<&InputDevices>,
+            Has<FreeLook>,
+        ),
+        With<Controlled>,
+    >,
+) {
+    for (mut input_map, &player, devices, free_look) in &mut input_maps {
+        *input_map = ship_input_map(&bindings.map, devices);
+        let keyboard_mouse = devices.is_none_or(|devices| devices.keyboard_mouse);
+        report_unmapped_actions(player, &input_map, keyboard_mouse);
+        if free_look {
+            hand_over_free_look_stick(&mut input_map);
+        }
+    }
+    commands.insert_resource(global_input_map(&bindings.map));
+    if let Err(error) = bindings.write(Path::new(INPUT_CONFIG_PATH)) {
+        error!("{error}");
+    }
+}
+
+/// Gives the free look stick back to flying the ship when the player stops looking around, or
+/// takes it away when they start.
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+#[expect(clippy::type_complexity, reason = "Bevy Query syntax")]
+fn apply_free_look(
+    bindings: Res<InputBindings>,
+    mut removed: RemovedComponents<FreeLook>,
+    added: Query<(), Added<FreeLook>>,
+    mut ships: Query<
+        (
+            Entity,
+            &mut InputMap<Action>,
+            Option<&InputDevices>,
+            Has<FreeLook>,
+        ),
+        With<Controlled>,
+    >,
+) {
+    let removed = removed.read().collect::<Vec<_>>();
+    for (entity, mut input_map, devices, free_look) in &mut ships {
+        if !added.contains(entity) && !removed.contains(&entity) {
+            continue;
+        }
+        *input_map = ship_input_map(&bindings.map, devices);
+        if free_look {
+            hand_over_free_look_stick(&mut input_map);
+        }
+    }
+}
+
+/// The bindings for a controlled ship, limited to its player's devices when it has some.
+fn ship_input_map(bindings: &InputMap<Action>, devices: Option<&InputDevices>) -> InputMap<Action> {
+    devices.map_or_else(|| bindings.clone(), |devices| devices.input_map(bindings))
+}
+
+/// The bindings of the [`Action::is_global`] actions, on every device.
+fn global_input_map(bindings: &InputMap<Action>) -> InputMap<Action> {
+    let mut input_map = InputMap::default();
+    for (&action, inputs) in bindings.iter() {
+        if action.is_global() {
+            for input in inputs {
+                _ = input_map.insert(action, input.clone());
+            }
+        }
+    }
+    input_map
+}
+
 // #[derive(Default)]
 // struct ActionEventStateData {
 //     button_state: ButtonState,
@@ -82,6 +938,7 @@ fn default_input_map() -> InputMap<Action> {
 // }
 
 #[autodefault]
+#[expect(clippy::too_many_lines, reason = "One entry per action")]
 fn default_action_map() -> HashMap<Action, (ButtonState, ActionEventData)> {
     let action_map: HashMap<_, _> = [
         (
@@ -119,6 +976,86 @@ fn default_action_map() -> HashMap<Action, (ButtonState, ActionEventData)> {
                 ActionEventData { auto_balance: 1.0 },
             ),
         ),
+        (
+            Action::FormUp,
+            (ButtonState::JustPressed, ActionEventData { form_up: 1.0 }),
+        ),
+        (
+            Action::BreakFormation,
+            (
+                ButtonState::JustPressed,
+                ActionEventData {
+                    break_formation: 1.0,
+                },
+            ),
+        ),
+        (
+            Action::AttackMyTarget,
+            (
+                ButtonState::JustPressed,
+                ActionEventData {
+                    attack_my_target: 1.0,
+                },
+            ),
+        ),
+        (
+            Action::TargetNearest,
+            (
+                ButtonState::JustPressed,
+                ActionEventData {
+                    target_nearest: 1.0,
+                },
+            ),
+        ),
+        (
+            Action::TargetNext,
+            (
+                ButtonState::JustPressed,
+                ActionEventData { target_next: 1.0 },
+            ),
+        ),
+        (
+            Action::TargetPrevious,
+            (
+                ButtonState::JustPressed,
+                ActionEventData {
+                    target_previous: 1.0,
+                },
+            ),
+        ),
+        (
+            Action::Jettison,
+            (ButtonState::JustPressed, ActionEventData { jettison: 1.0 }),
+        ),
+        (
+            Action::MiningLaser,
+            (ButtonState::Pressed, ActionEventData { mining_laser: 1.0 }),
+        ),
+        (
+            Action::Scan,
+            (ButtonState::JustPressed, ActionEventData { scan: 1.0 }),
+        ),
+        (
+            Action::MarketNext,
+            (
+                ButtonState::JustPressed,
+                ActionEventData { market_next: 1.0 },
+            ),
+        ),
+        (
+            Action::MarketBuy,
+            (
+                ButtonState::JustPressed,
+                ActionEventData { market_buy: 1.0 },
+            ),
+        ),
+        (
+            Action::MarketSell,
+            (
+                ButtonState::JustPressed,
+                ActionEventData { market_sell: 1.0 },
+            ),
+        ),
     ]
     .iter()
     .copied()
@@ -130,15 +1067,49 @@ fn default_action_map() -> HashMap<Action, (ButtonState, ActionEventData)> {
 #[derive(Component)]
 pub struct Controlled {
     action_map: HashMap<Action, (ButtonState, ActionEventData)>,
+    /// Where the throttle lever is set, from full reverse to full forward.
+    throttle_lever: f32,
+}
+
+/// The actions a player can't use because nothing in their input map triggers them, mouse flight
+/// only counts for whoever has the mouse.
+fn unmapped_actions(input_map: &InputMap<Action>, keyboard_mouse: bool) -> Vec<Action> {
+    Action::ALL
+        .into_iter()
+        .filter(|&action| keyboard_mouse || action != Action::MouseFlight)
+        .filter(|action| input_map.get(action).is_none_or(Vec::is_empty))
+        .collect()
+}
+
+/// Warns about actions that can't be used, which then just never fire instead of failing.
+fn report_unmapped_actions(player: PlayerId, input_map: &InputMap<Action>, keyboard_mouse: bool) {
+    let unmapped = unmapped_actions(input_map, keyboard_mouse);
+    if !unmapped.is_empty() {
+        let actions = unmapped
+            .iter()
+            .map(|action| format!("{action:?}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        warn!("{player} has nothing bound to {actions}");
+    }
 }
 
 #[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
-fn on_ship_created_add_input(mut commands: Commands, query: Query<Entity, Added<Ship>>) {
-    for entity in query.iter() {
+fn on_ship_created_add_input(
+    mut commands: Commands,
+    bindings: Res<InputBindings>,
+    query: Query<(Entity, &Controller), Added<Controller>>,
+) {
+    // Everyone else's ships get their inputs from their own controller
+    for (entity, _) in query
+        .iter()
+        .filter(|(_, controller)| **controller == Controller::LocalPlayer)
+    {
         _ = commands.entity(entity).insert((
-            InputManagerBundle::with_map(default_input_map()),
+            InputManagerBundle::with_map(bindings.map.clone()),
             Controlled {
                 action_map: default_action_map(),
+                throttle_lever: 0.0,
             },
         ));
     }
@@ -147,22 +1118,239 @@ fn on_ship_created_add_input(mut commands: Commands, query: Query<Entity, Added<
 #[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
 fn process_inputs(
     mut commands: Commands,
-    query: Query<(&ActionState<Action>, &Controlled, Entity), With<Controlled>>,
+    bindings: Res<InputBindings>,
+    mouse_flight: Res<MouseFlight>,
+    time: Res<Time>,
+    mut query: Query<(
+        &ActionState<Action>,
+        &mut Controlled,
+        Option<&InputDevices>,
+        Entity,
+    )>,
 ) {
-    for (action_state, controlled, entity) in &query {
+    for (action_state, mut controlled, devices, entity) in &mut query {
         let mut action_data = ActionEventData::default();
 
         for (action, &(button_state_expected, action_event_data)) in &controlled.action_map {
-            let action_button_state = action_state
-                .action_data(action)
-                .expect("Action data not found for the given action")
-                .state;
-            if action_state.pressed(action) && action_button_state == button_state_expected {
+            // An action without data has never been triggered, which is the same as no input
+            let Some(action_data_state) = action_state.action_data(action) else {
+                continue;
+            };
+            if action_state.pressed(action) && action_data_state.state == button_state_expected {
                 let value = action_state.clamped_value(action);
+                let value = bindings
+                    .axes
+                    .get(*action)
+                    .map_or(value, |response| response.apply(value));
                 action_data += action_event_data * value;
             }
         }
 
+        if bindings.throttle == ThrottleMode::Lever {
+            let lever = action_data.thrust.mul_add(
+                THROTTLE_LEVER_RATE * time.delta_seconds(),
+                controlled.throttle_lever,
+            );
+            controlled.throttle_lever = lever.clamp(-1.0, 1.0);
+            action_data.thrust = controlled.throttle_lever;
+        }
+
+        // The mouse belongs to whoever has the keyboard
+        if devices.is_none_or(|devices| devices.keyboard_mouse) {
+            let stick = mouse_flight.stick(&bindings.mouse_flight);
+            action_data.yaw = (action_data.yaw + stick.x).clamp(-1.0, 1.0);
+            action_data.pitch = (action_data.pitch + stick.y).clamp(-1.0, 1.0);
+        }
+
         _ = commands.entity(entity).insert(action_data);
     }
 }
+
+/// Lets go of every control so ships that keep flying while paused don't keep thrusting.
+fn release_controls(mut query: Query<&mut ActionEventData, With<Controlled>>) {
+    for mut action_event_data in &mut query {
+        *action_event_data = ActionEventData::default();
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn request_quicksave(
+    action_state: Res<ActionState<Action>>,
+    mut save_requests: EventWriter<SaveRequested>,
+) {
+    if action_state.just_pressed(&Action::Quicksave) {
+        _ = save_requests.send(SaveRequested {
+            path: QUICKSAVE_PATH.to_owned(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::InputPlugin as BevyInputPlugin;
+    use leafwing_input_manager::input_mocking::MockInput;
+
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn linear_curve_passes_values_through() {
+        for deflection in [0.0, 0.25, 0.5, 1.0] {
+            assert_close(ResponseCurve::Linear.evaluate(deflection), deflection);
+        }
+    }
+
+    #[test]
+    fn exponential_curve_softens_the_center() {
+        let curve = ResponseCurve::Exponential(2.0);
+        assert_close(curve.evaluate(0.0), 0.0);
+        assert_close(curve.evaluate(0.5), 0.25);
+        assert_close(curve.evaluate(1.0), 1.0);
+    }
+
+    #[test]
+    fn custom_curve_interpolates_between_points() {
+        let curve = ResponseCurve::Custom(vec![(0.5, 0.2), (0.8, 0.9)]);
+        assert_close(curve.evaluate(0.25), 0.1);
+        assert_close(curve.evaluate(0.5), 0.2);
+        assert_close(curve.evaluate(0.65), 0.55);
+        assert_close(curve.evaluate(0.9), 0.95);
+        assert_close(curve.evaluate(1.5), 1.0);
+    }
+
+    #[test]
+    fn deadzones_rescale_the_remaining_range() {
+        let response = AxisResponse {
+            inner_deadzone: 0.2,
+            outer_deadzone: 0.8,
+            ..default()
+        };
+        assert_close(response.apply(0.1), 0.0);
+        assert_close(response.apply(-0.2), 0.0);
+        assert_close(response.apply(0.5), 0.5);
+        assert_close(response.apply(-0.5), -0.5);
+        assert_close(response.apply(0.9), 1.0);
+    }
+
+    #[test]
+    fn stick_values_below_the_default_deadzone_reach_the_axis_response() {
+        let gamepad = Gamepad::new(0);
+        let mut input_map = default_input_map();
+        _ = input_map.set_gamepad(gamepad);
+
+        let mut app = App::new();
+        _ = app.add_plugins((
+            MinimalPlugins,
+            BevyInputPlugin,
+            InputManagerPlugin::<Action>::default(),
+        ));
+        let ship = app
+            .world_mut()
+            .spawn(InputManagerBundle::with_map(input_map))
+            .id();
+        _ = app
+            .world_mut()
+            .resource_mut::<Axis<GamepadAxis>>()
+            .set(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX), 0.05);
+        app.update();
+
+        let value = app
+            .world()
+            .get::<ActionState<Action>>(ship)
+            .expect("Ship has an action state")
+            .clamped_value(&Action::Aileron);
+        assert_close(value, 0.05);
+        let response = AxisResponse {
+            inner_deadzone: 0.02,
+            ..default()
+        };
+        assert!(response.apply(value) > 0.0);
+    }
+
+    #[test]
+    fn gamepad_only_players_can_use_every_action() {
+        let devices = InputDevices {
+            keyboard_mouse: false,
+            gamepad: GamepadAssignment::Only(Gamepad::new(1)),
+        };
+        let input_map = devices.input_map(&default_input_map());
+        for action in Action::ALL {
+            assert!(
+                action == Action::MouseFlight || input_map.get(&action).is_some(),
+                "{action:?} has no gamepad binding"
+            );
+        }
+        assert_eq!(unmapped_actions(&input_map, false), vec![]);
+        assert!(unmapped_actions(&InputMap::default(), true).contains(&Action::MouseFlight));
+    }
+
+    #[test]
+    fn default_bindings_only_share_buttons_through_chords() {
+        assert_eq!(InputBindings::default().conflicts(), vec![]);
+
+        let mut bindings = InputBindings::default();
+        bindings.rebind(
+            Action::Scan,
+            UserInput::chord([GAMEPAD_MODIFIER, GamepadButtonType::DPadLeft]),
+        );
+        let conflicts = bindings.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts.first().map(|(_, actions)| actions.as_slice()),
+            Some([Action::Scan, Action::MarketBuy].as_slice())
+        );
+    }
+
+    #[test]
+    fn free_look_takes_the_right_stick_off_the_rudder_only() {
+        let mut input_map = default_input_map();
+        hand_over_free_look_stick(&mut input_map);
+        let rudder = input_map.get(&Action::Rudder).expect("Rudder stays bound");
+        assert_eq!(rudder.len(), 1);
+        assert!(rudder.iter().all(|input| !is_gamepad_input(input)));
+        assert_eq!(input_map.get(&Action::Aileron).map(Vec::len), Some(2));
+    }
+
+    #[test]
+    fn global_actions_work_without_a_ship() {
+        let mut app = App::new();
+        _ = app
+            .add_plugins((
+                MinimalPlugins,
+                BevyInputPlugin,
+                InputManagerPlugin::<Action>::default(),
+            ))
+            .init_resource::<ActionState<Action>>()
+            .insert_resource(global_input_map(&default_input_map()));
+        app.send_input(KeyCode::Escape);
+        app.send_input(KeyCode::F5);
+        app.update();
+
+        let action_state = app.world().resource::<ActionState<Action>>();
+        assert!(action_state.just_pressed(&Action::Pause));
+        assert!(action_state.just_pressed(&Action::Quicksave));
+        assert!(global_input_map(&default_input_map())
+            .iter()
+            .all(|(action, _)| action.is_global()));
+    }
+
+    #[test]
+    fn inversion_and_sensitivity_scale_the_shaped_value() {
+        let response = AxisResponse {
+            inner_deadzone: 0.0,
+            curve: ResponseCurve::Exponential(2.0),
+            inverted: true,
+            sensitivity: 1.5,
+            ..default()
+        };
+        assert_close(response.apply(0.5), -0.375);
+        assert_close(response.apply(-0.5), 0.375);
+        assert_close(response.apply(1.0), -1.0);
+    }
+}
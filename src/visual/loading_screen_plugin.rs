@@ -0,0 +1,128 @@
+use autodefault::autodefault;
+use bevy::prelude::*;
+
+use crate::game::{loading_plugin::LoadingProgress, states_plugin::MainState};
+
+#[derive(Debug)]
+pub struct LoadingScreenPlugin;
+
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(OnEnter(MainState::Loading), setup)
+            .add_systems(
+                Update,
+                (update_loading_screen, return_to_menu).run_if(in_state(MainState::Loading)),
+            );
+    }
+}
+
+#[derive(Component, Debug)]
+struct LoadingStatus;
+
+#[derive(Component, Debug)]
+struct LoadingBar;
+
+const BAR_BACKGROUND_COLOR: Color = Color::srgb(0.15, 0.15, 0.2);
+const BAR_COLOR: Color = Color::srgb(0.35, 0.45, 0.6);
+const FAILURE_COLOR: Color = Color::srgb(0.9, 0.3, 0.3);
+
+#[autodefault]
+fn setup(mut commands: Commands) {
+    _ = commands.spawn((Camera2dBundle::default(), StateScoped(MainState::Loading)));
+    _ = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(12.0),
+                },
+            },
+            StateScoped(MainState::Loading),
+        ))
+        .with_children(|parent| {
+            _ = parent.spawn(
+                TextBundle::from_section("Loading", TextStyle { font_size: 64.0 }).with_style(
+                    Style {
+                        margin: UiRect::bottom(Val::Px(36.0)),
+                    },
+                ),
+            );
+            _ = parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(400.0),
+                        height: Val::Px(16.0),
+                    },
+                    background_color: BAR_BACKGROUND_COLOR.into(),
+                })
+                .with_children(|parent| {
+                    _ = parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                            },
+                            background_color: BAR_COLOR.into(),
+                        },
+                        LoadingBar,
+                    ));
+                });
+            _ = parent.spawn((
+                TextBundle::from_section("", TextStyle { font_size: 20.0 }),
+                LoadingStatus,
+            ));
+        });
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_loading_screen(
+    progress: Res<LoadingProgress>,
+    mut bars: Query<&mut Style, With<LoadingBar>>,
+    mut statuses: Query<&mut Text, With<LoadingStatus>>,
+) {
+    if !progress.is_changed() {
+        return;
+    }
+
+    for mut bar in &mut bars {
+        bar.width = Val::Percent(progress.fraction() * 100.0);
+    }
+
+    let (status, color) = if progress.failures.is_empty() {
+        (
+            format!("{} / {} assets", progress.done, progress.total),
+            Color::WHITE,
+        )
+    } else {
+        (
+            format!(
+                "Failed to load:\n{}\nPress Escape to return to the menu",
+                progress.failures.join("\n")
+            ),
+            FAILURE_COLOR,
+        )
+    };
+    for mut text in &mut statuses {
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&status);
+            section.style.color = color;
+        }
+    }
+}
+
+/// Lets the player back out when loading has failed and won't finish.
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn return_to_menu(
+    keys: Res<ButtonInput<KeyCode>>,
+    progress: Res<LoadingProgress>,
+    mut next_main_state: ResMut<NextState<MainState>>,
+) {
+    if !progress.failures.is_empty() && keys.just_pressed(KeyCode::Escape) {
+        next_main_state.set(MainState::MainMenu);
+    }
+}
@@ -0,0 +1,708 @@
+use autodefault::autodefault;
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow, WindowMode},
+};
+
+use leafwing_input_manager::{axislike::VirtualAxis, prelude::*};
+
+use super::input_plugin::{input_label, kind_label, Action, InputBindings};
+use crate::{
+    cli::CommandLineArguments,
+    game::states_plugin::{GameModeState, InGameState, MainState},
+};
+
+#[derive(Debug)]
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_sub_state::<MenuScreen>()
+            .add_sub_state::<PauseScreen>()
+            .enable_state_scoped_entities::<MenuScreen>()
+            .enable_state_scoped_entities::<PauseScreen>()
+            .init_resource::<BindingCapture>()
+            .add_systems(OnEnter(MainState::MainMenu), setup)
+            .add_systems(OnEnter(MenuScreen::Main), spawn_main_screen)
+            .add_systems(
+                OnEnter(MenuScreen::Settings),
+                spawn_settings_screen::<MenuScreen>,
+            )
+            .add_systems(
+                OnEnter(MenuScreen::Controls),
+                spawn_controls_screen::<MenuScreen>,
+            )
+            .add_systems(OnEnter(PauseScreen::Main), spawn_pause_screen)
+            .add_systems(
+                OnEnter(PauseScreen::Settings),
+                spawn_settings_screen::<PauseScreen>,
+            )
+            .add_systems(
+                OnEnter(PauseScreen::Controls),
+                spawn_controls_screen::<PauseScreen>,
+            )
+            .add_systems(OnExit(MainState::MainMenu), stop_capture)
+            .add_systems(OnExit(InGameState::Paused), stop_capture)
+            // Going back from the controls page cancels a pending capture
+            .add_systems(OnExit(MenuScreen::Controls), stop_capture)
+            .add_systems(OnExit(PauseScreen::Controls), stop_capture)
+            .add_systems(
+                Update,
+                (
+                    highlight_buttons,
+                    // Before the buttons, so the click starting a capture isn't captured
+                    capture_binding.before(press_controls_buttons),
+                    press_main_menu_buttons.run_if(in_state(MainState::MainMenu)),
+                    press_pause_menu_buttons.run_if(in_state(InGameState::Paused)),
+                    press_settings_buttons,
+                    press_controls_buttons,
+                    update_setting_labels,
+                    update_controls_status,
+                ),
+            )
+            .add_systems(
+                Update,
+                toggle_pause
+                    .run_if(in_state(MainState::InGame))
+                    // Escape cancels the capture instead, which consumes the press
+                    .after(capture_binding)
+                    .run_if(not(capturing_binding)),
+            );
+    }
+}
+
+/// Which page of the main menu is showing.
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(MainState = MainState::MainMenu)]
+enum MenuScreen {
+    #[default]
+    Main,
+    Settings,
+    Controls,
+}
+
+/// Which page of the pause overlay is showing.
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(InGameState = InGameState::Paused)]
+enum PauseScreen {
+    #[default]
+    Main,
+    Settings,
+    Controls,
+}
+
+/// Menus with settings pages, which are scoped to the menu they were opened from.
+trait SettingsScreen: States {
+    const SETTINGS: Self;
+    const CONTROLS: Self;
+
+    /// The page the back button leads to.
+    fn back(&self) -> Self;
+}
+
+impl SettingsScreen for MenuScreen {
+    const SETTINGS: Self = Self::Settings;
+    const CONTROLS: Self = Self::Controls;
+
+    fn back(&self) -> Self {
+        match self {
+            Self::Controls => Self::Settings,
+            _ => Self::Main,
+        }
+    }
+}
+
+impl SettingsScreen for PauseScreen {
+    const SETTINGS: Self = Self::Settings;
+    const CONTROLS: Self = Self::Controls;
+
+    fn back(&self) -> Self {
+        match self {
+            Self::Controls => Self::Settings,
+            _ => Self::Main,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuButton {
+    NewGame,
+    JoinSession,
+    Settings,
+    Quit,
+    Resume,
+    QuitToMenu,
+    ToggleFullscreen,
+    ToggleVsync,
+    Controls,
+    Rebind(Action),
+    ResetBindings,
+    Back,
+}
+
+/// The action waiting for the player to press its new input, if any.
+#[derive(Resource, Default, Debug)]
+struct BindingCapture {
+    action: Option<Action>,
+    /// First key of a keyboard axis, which takes one key per direction.
+    negative: Option<KeyCode>,
+}
+
+/// Shows conflicting bindings, or how to rebind when there are none.
+#[derive(Component, Debug)]
+struct ControlsStatus;
+
+const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.2);
+const BUTTON_HOVERED_COLOR: Color = Color::srgb(0.25, 0.25, 0.35);
+const BUTTON_PRESSED_COLOR: Color = Color::srgb(0.35, 0.45, 0.6);
+/// Dims the game behind the pause overlay.
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const CONFLICT_COLOR: Color = Color::srgb(0.9, 0.3, 0.3);
+const HINT_COLOR: Color = Color::srgb(0.7, 0.7, 0.7);
+
+fn setup(mut commands: Commands) {
+    _ = commands.spawn((Camera2dBundle::default(), StateScoped(MainState::MainMenu)));
+}
+
+fn spawn_main_screen(mut commands: Commands) {
+    spawn_screen(
+        &mut commands,
+        MenuScreen::Main,
+        "Spacerama",
+        &[
+            (MenuButton::NewGame, "New Game"),
+            (MenuButton::JoinSession, "Join Session"),
+            (MenuButton::Settings, "Settings"),
+            (MenuButton::Quit, "Quit"),
+        ],
+    );
+}
+
+fn spawn_pause_screen(mut commands: Commands) {
+    spawn_screen(
+        &mut commands,
+        PauseScreen::Main,
+        "Paused",
+        &[
+            (MenuButton::Resume, "Resume"),
+            (MenuButton::Settings, "Settings"),
+            (MenuButton::QuitToMenu, "Quit to Menu"),
+        ],
+    );
+}
+
+fn spawn_settings_screen<S: SettingsScreen>(mut commands: Commands) {
+    // Labels are filled in by `update_setting_labels` once the current values are known
+    spawn_screen(
+        &mut commands,
+        S::SETTINGS,
+        "Settings",
+        &[
+            (MenuButton::ToggleFullscreen, ""),
+            (MenuButton::ToggleVsync, ""),
+            (MenuButton::Controls, "Controls"),
+            (MenuButton::Back, "Back"),
+        ],
+    );
+}
+
+#[autodefault]
+fn spawn_screen<S: States>(
+    commands: &mut Commands,
+    screen: S,
+    title: &str,
+    buttons: &[(MenuButton, &str)],
+) {
+    _ = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(12.0),
+                },
+                background_color: OVERLAY_COLOR.into(),
+                // Above the HUD when drawn over the game
+                z_index: ZIndex::Global(1),
+            },
+            StateScoped(screen),
+        ))
+        .with_children(|parent| {
+            _ = parent.spawn(
+                TextBundle::from_section(title, TextStyle { font_size: 64.0 }).with_style(Style {
+                    margin: UiRect::bottom(Val::Px(36.0)),
+                }),
+            );
+
+            for &(button, label) in buttons {
+                spawn_button(parent, button, label, Vec2::new(280.0, 56.0), 28.0);
+            }
+        });
+}
+
+#[autodefault]
+fn spawn_controls_screen<S: SettingsScreen>(mut commands: Commands) {
+    // Labels are filled in by `update_setting_labels` from the current bindings
+    _ = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(12.0),
+                },
+                background_color: OVERLAY_COLOR.into(),
+                z_index: ZIndex::Global(1),
+            },
+            StateScoped(S::CONTROLS),
+        ))
+        .with_children(|parent| {
+            _ = parent.spawn(TextBundle::from_section(
+                "Controls",
+                TextStyle { font_size: 48.0 },
+            ));
+
+            _ = parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Percent(90.0),
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        column_gap: Val::Px(8.0),
+                        row_gap: Val::Px(6.0),
+                    },
+                })
+                .with_children(|parent| {
+                    for action in Action::ALL {
+                        spawn_button(
+                            parent,
+                            MenuButton::Rebind(action),
+                            "",
+                            Vec2::new(420.0, 30.0),
+                            16.0,
+                        );
+                    }
+                });
+
+            _ = parent.spawn((
+                TextBundle::from_section("", TextStyle { font_size: 18.0 }),
+                ControlsStatus,
+            ));
+
+            _ = parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(12.0),
+                    },
+                })
+                .with_children(|parent| {
+                    for (button, label) in [
+                        (MenuButton::ResetBindings, "Reset to Defaults"),
+                        (MenuButton::Back, "Back"),
+                    ] {
+                        spawn_button(parent, button, label, Vec2::new(280.0, 56.0), 28.0);
+                    }
+                });
+        });
+}
+
+#[autodefault]
+fn spawn_button(
+    parent: &mut ChildBuilder,
+    button: MenuButton,
+    label: &str,
+    size: Vec2,
+    font_size: f32,
+) {
+    _ = parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(size.x),
+                    height: Val::Px(size.y),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                },
+                background_color: BUTTON_COLOR.into(),
+            },
+            button,
+        ))
+        .with_children(|parent| {
+            _ = parent.spawn(TextBundle::from_section(label, TextStyle { font_size }));
+        });
+}
+
+#[expect(clippy::type_complexity, reason = "Bevy Query syntax")]
+fn highlight_buttons(
+    mut buttons: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, mut background_color) in &mut buttons {
+        *background_color = match interaction {
+            Interaction::Pressed => BUTTON_PRESSED_COLOR,
+            Interaction::Hovered => BUTTON_HOVERED_COLOR,
+            Interaction::None => BUTTON_COLOR,
+        }
+        .into();
+    }
+}
+
+/// Buttons that have just been clicked.
+fn pressed(buttons: &Query<(&Interaction, &MenuButton), Changed<Interaction>>) -> Vec<MenuButton> {
+    buttons
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, &button)| button)
+        .collect()
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn press_main_menu_buttons(
+    args: Option<Res<CommandLineArguments>>,
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut next_main_state: ResMut<NextState<MainState>>,
+    mut next_game_mode_state: ResMut<NextState<GameModeState>>,
+    mut next_menu_screen: ResMut<NextState<MenuScreen>>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    for button in pressed(&buttons) {
+        match button {
+            MenuButton::NewGame => {
+                next_game_mode_state.set(GameModeState::Singleplayer);
+                next_main_state.set(MainState::Loading);
+            }
+            MenuButton::JoinSession => {
+                if let Some(args) = &args {
+                    info!("Joining session {}", args.session_id);
+                }
+                next_game_mode_state.set(GameModeState::Multiplayer);
+                next_main_state.set(MainState::Loading);
+            }
+            MenuButton::Settings => next_menu_screen.set(MenuScreen::Settings),
+            MenuButton::Quit => _ = app_exit.send(AppExit::Success),
+            _ => {}
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn press_pause_menu_buttons(
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut next_main_state: ResMut<NextState<MainState>>,
+    mut next_in_game_state: ResMut<NextState<InGameState>>,
+    mut next_pause_screen: ResMut<NextState<PauseScreen>>,
+) {
+    for button in pressed(&buttons) {
+        match button {
+            MenuButton::Resume => next_in_game_state.set(InGameState::Running),
+            MenuButton::Settings => next_pause_screen.set(PauseScreen::Settings),
+            MenuButton::QuitToMenu => next_main_state.set(MainState::MainMenu),
+            _ => {}
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn press_settings_buttons(
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    menu_screen: Option<Res<State<MenuScreen>>>,
+    pause_screen: Option<Res<State<PauseScreen>>>,
+    mut next_menu_screen: ResMut<NextState<MenuScreen>>,
+    mut next_pause_screen: ResMut<NextState<PauseScreen>>,
+) {
+    for button in pressed(&buttons) {
+        match button {
+            MenuButton::ToggleFullscreen => {
+                for mut window in &mut windows {
+                    window.mode = if window.mode == WindowMode::Windowed {
+                        WindowMode::BorderlessFullscreen
+                    } else {
+                        WindowMode::Windowed
+                    };
+                }
+            }
+            MenuButton::ToggleVsync => {
+                for mut window in &mut windows {
+                    window.present_mode = if window.present_mode == PresentMode::AutoNoVsync {
+                        PresentMode::AutoVsync
+                    } else {
+                        PresentMode::AutoNoVsync
+                    };
+                }
+            }
+            // Only one of the menus can be open at a time
+            MenuButton::Controls if menu_screen.is_some() => {
+                next_menu_screen.set(MenuScreen::Controls);
+            }
+            MenuButton::Controls if pause_screen.is_some() => {
+                next_pause_screen.set(PauseScreen::Controls);
+            }
+            MenuButton::Back => {
+                if let Some(menu_screen) = &menu_screen {
+                    next_menu_screen.set(menu_screen.get().back());
+                }
+                if let Some(pause_screen) = &pause_screen {
+                    next_pause_screen.set(pause_screen.get().back());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn press_controls_buttons(
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut bindings: ResMut<InputBindings>,
+    mut capture: ResMut<BindingCapture>,
+) {
+    for button in pressed(&buttons) {
+        match button {
+            MenuButton::Rebind(action) => {
+                *capture = BindingCapture {
+                    action: Some(action),
+                    negative: None,
+                };
+            }
+            MenuButton::ResetBindings => {
+                *bindings = InputBindings::default();
+                *capture = BindingCapture::default();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Binds the next key, mouse button, gamepad button or stick movement to the action being rebound.
+#[expect(
+    clippy::too_many_arguments,
+    clippy::needless_pass_by_value,
+    reason = "Bevy System syntax"
+)]
+fn capture_binding(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    buttons: Query<&Interaction, With<Button>>,
+    mut global_actions: ResMut<ActionState<Action>>,
+    mut bindings: ResMut<InputBindings>,
+    mut capture: ResMut<BindingCapture>,
+) {
+    let Some(action) = capture.action else {
+        return;
+    };
+    // Clicks on the menu's own buttons are for the menu, not the binding
+    let over_button = buttons
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    if keys.just_pressed(KeyCode::Escape) {
+        *capture = BindingCapture::default();
+        // The press that ends a capture is used up, it mustn't pause or resume the game too
+        consume_global_actions(&mut global_actions);
+        return;
+    }
+
+    let key = keys.get_just_pressed().next().copied();
+    let input = if action.is_axis() {
+        let stick = gamepads.iter().find_map(|gamepad| {
+            STICK_AXES.into_iter().find(|&axis_type| {
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, axis_type))
+                    .is_some_and(|value| value.abs() > CAPTURE_AXIS_THRESHOLD)
+            })
+        });
+        match (stick, key, capture.negative) {
+            (Some(axis_type), ..) => Some(UserInput::from(SingleAxis::symmetric(axis_type, 0.0))),
+            (None, Some(positive), Some(negative)) => {
+                Some(VirtualAxis::from_keys(negative, positive).into())
+            }
+            (None, Some(negative), None) => {
+                capture.negative = Some(negative);
+                None
+            }
+            (None, None, _) => None,
+        }
+    } else {
+        key.map(UserInput::from)
+            .or_else(|| {
+                mouse_buttons
+                    .get_just_pressed()
+                    .next()
+                    .filter(|_| !over_button)
+                    .map(|&button| button.into())
+            })
+            .or_else(|| {
+                gamepad_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| button.button_type.into())
+            })
+    };
+
+    if let Some(input) = input {
+        bindings.rebind(action, input);
+        *capture = BindingCapture::default();
+        consume_global_actions(&mut global_actions);
+    }
+}
+
+fn consume_global_actions(action_state: &mut ActionState<Action>) {
+    for action in Action::ALL.into_iter().filter(|action| action.is_global()) {
+        action_state.consume(&action);
+    }
+}
+
+/// Sticks that can be bound to an axis action, triggers are bound as buttons.
+const STICK_AXES: [GamepadAxisType; 4] = [
+    GamepadAxisType::LeftStickX,
+    GamepadAxisType::LeftStickY,
+    GamepadAxisType::RightStickX,
+    GamepadAxisType::RightStickY,
+];
+
+/// How far a stick has to move to be captured, so resting drift doesn't get bound.
+const CAPTURE_AXIS_THRESHOLD: f32 = 0.5;
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn capturing_binding(capture: Res<BindingCapture>) -> bool {
+    capture.action.is_some()
+}
+
+fn stop_capture(mut capture: ResMut<BindingCapture>) {
+    *capture = BindingCapture::default();
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn toggle_pause(
+    action_state: Res<ActionState<Action>>,
+    in_game_state: Res<State<InGameState>>,
+    mut next_in_game_state: ResMut<NextState<InGameState>>,
+) {
+    // Any device can pause, whether or not it flies a ship
+    if !action_state.just_pressed(&Action::Pause) {
+        return;
+    }
+
+    next_in_game_state.set(match in_game_state.get() {
+        InGameState::Running => InGameState::Paused,
+        InGameState::Paused => InGameState::Running,
+    });
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_setting_labels(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    bindings: Res<InputBindings>,
+    capture: Res<BindingCapture>,
+    buttons: Query<(&MenuButton, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    let Some(window) = windows.iter().next() else {
+        return;
+    };
+
+    for (button, children) in &buttons {
+        let label = match button {
+            MenuButton::ToggleFullscreen => format!(
+                "Fullscreen: {}",
+                on_off(window.mode != WindowMode::Windowed)
+            ),
+            MenuButton::ToggleVsync => format!(
+                "VSync: {}",
+                on_off(window.present_mode != PresentMode::AutoNoVsync)
+            ),
+            &MenuButton::Rebind(action) => binding_label(action, &bindings, &capture),
+            _ => continue,
+        };
+
+        let mut texts = texts.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            if let Some(section) = text.sections.first_mut() {
+                if section.value != label {
+                    section.value.clone_from(&label);
+                }
+            }
+        }
+    }
+}
+
+fn binding_label(action: Action, bindings: &InputBindings, capture: &BindingCapture) -> String {
+    let inputs = if capture.action == Some(action) {
+        match capture.negative {
+            Some(negative) => format!("{negative:?} / press the key for positive"),
+            None if action.is_axis() => "move a stick or press the key for negative".to_owned(),
+            None => "press a key or button".to_owned(),
+        }
+    } else {
+        let inputs = bindings
+            .map
+            .get(&action)
+            .into_iter()
+            .flatten()
+            .map(input_label)
+            .collect::<Vec<_>>();
+        if inputs.is_empty() {
+            "unbound".to_owned()
+        } else {
+            inputs.join(", ")
+        }
+    };
+    format!("{action:?}: {inputs}")
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn update_controls_status(
+    bindings: Res<InputBindings>,
+    capture: Res<BindingCapture>,
+    mut statuses: Query<&mut Text, With<ControlsStatus>>,
+) {
+    let conflicts = bindings.conflicts();
+    let (status, color) = if capture.action.is_some() {
+        ("Press Escape to cancel".to_owned(), HINT_COLOR)
+    } else if conflicts.is_empty() {
+        ("Click an action to rebind it".to_owned(), HINT_COLOR)
+    } else {
+        let conflicts = conflicts
+            .iter()
+            .map(|(kinds, actions)| {
+                let input = kinds.iter().map(kind_label).collect::<Vec<_>>();
+                let actions = actions
+                    .iter()
+                    .map(|action| format!("{action:?}"))
+                    .collect::<Vec<_>>();
+                format!(
+                    "{} is bound to {}",
+                    input.join(" + "),
+                    actions.join(" and ")
+                )
+            })
+            .collect::<Vec<_>>();
+        (conflicts.join("\n"), CONFLICT_COLOR)
+    };
+
+    for mut text in &mut statuses {
+        if let Some(section) = text.sections.first_mut() {
+            if section.value != status {
+                section.value.clone_from(&status);
+                section.style.color = color;
+            }
+        }
+    }
+}
+
+const fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "On"
+    } else {
+        "Off"
+    }
+}
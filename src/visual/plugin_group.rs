@@ -1,6 +1,12 @@
 use bevy::app::{PluginGroup, PluginGroupBuilder};
 
-use super::{rendering_setup_plugin::RenderingSetupPlugin, ship_plugin::ShipPlugin};
+use super::{
+    asteroid_plugin::AsteroidPlugin, camera_plugin::CameraPlugin, cargo_plugin::CargoPlugin,
+    exploration_plugin::ExplorationPlugin, hud_plugin::HudPlugin,
+    loading_screen_plugin::LoadingScreenPlugin, menu_plugin::MenuPlugin,
+    rendering_setup_plugin::RenderingSetupPlugin, ship_plugin::ShipPlugin,
+    split_screen_plugin::SplitScreenPlugin, station_plugin::StationPlugin,
+};
 use crate::visual::input_plugin::InputPlugin;
 
 #[expect(clippy::module_name_repetitions, reason = "This is a plugin group for the visual part of the game")]
@@ -10,7 +16,16 @@ impl PluginGroup for VisualPluginGroup {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(RenderingSetupPlugin)
+            .add(MenuPlugin)
+            .add(LoadingScreenPlugin)
             .add(ShipPlugin)
+            .add(CameraPlugin)
+            .add(SplitScreenPlugin)
             .add(InputPlugin)
+            .add(HudPlugin)
+            .add(CargoPlugin)
+            .add(AsteroidPlugin)
+            .add(StationPlugin)
+            .add(ExplorationPlugin)
     }
 }
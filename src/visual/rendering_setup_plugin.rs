@@ -41,14 +41,17 @@ fn setup(mut commands: Commands) {
     //     },
     // ));
 
-    _ = commands.spawn(DirectionalLightBundle {
-        directional_light: DirectionalLight {
-            shadows_enabled: true,
+    _ = commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                shadows_enabled: true,
+            },
+            cascade_shadow_config: CascadeShadowConfigBuilder {
+                num_cascades: 1,
+                // maximum_distance: 16.0,
+            }
+            .into(),
         },
-        cascade_shadow_config: CascadeShadowConfigBuilder {
-            num_cascades: 1,
-            // maximum_distance: 16.0,
-        }
-        .into(),
-    });
+        StateScoped(MainState::InGame),
+    ));
 }
@@ -2,9 +2,11 @@ use autodefault::autodefault;
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
+use super::camera_plugin::CockpitAnchor;
 use crate::game::{
+    loading_plugin::LoadingAppExt,
     ship_plugin::Ship,
-    states_plugin::{InGameState, MainState},
+    states_plugin::{simulation_running, MainState},
 };
 
 #[derive(Debug)]
@@ -12,16 +14,12 @@ pub struct ShipPlugin;
 
 impl Plugin for ShipPlugin {
     fn build(&self, app: &mut App) {
-        _ = app
-            .configure_loading_state(
-                LoadingStateConfig::new(MainState::Loading).load_collection::<ShipAssets>(),
-            )
-            .add_systems(
-                FixedUpdate,
-                on_ship_created_add_visuals
-                    .run_if(in_state(MainState::InGame))
-                    .run_if(in_state(InGameState::Running)),
-            );
+        _ = app.load_tracked_collection::<ShipAssets>().add_systems(
+            FixedUpdate,
+            on_ship_created_add_visuals
+                .run_if(in_state(MainState::InGame))
+                .run_if(simulation_running),
+        );
     }
 }
 
@@ -31,6 +29,9 @@ struct ShipAssets {
     ship_001_scene: Handle<Scene>,
 }
 
+/// Where the pilot sits in the ship model.
+const SHIP_001_COCKPIT: Vec3 = Vec3::new(0.0, 1.5, 2.5);
+
 #[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
 #[autodefault]
 fn on_ship_created_add_visuals(
@@ -39,14 +40,14 @@ fn on_ship_created_add_visuals(
     query: Query<Entity, Added<Ship>>,
 ) {
     for entity in query.iter() {
-        _ = commands.entity(entity).with_children(|parent| {
-            // let mesh = Capsule3d::new(0.5, 1.5);
-            _ = parent.spawn(SceneBundle {
-                scene: ship_assets.ship_001_scene.clone(),
-            });
-            _ = parent.spawn(Camera3dBundle {
-                transform: Transform::from_xyz(0.0, 4.5, -15.0).looking_at(Vec3::ZERO, Vec3::Y),
+        _ = commands
+            .entity(entity)
+            .insert(CockpitAnchor(SHIP_001_COCKPIT))
+            .with_children(|parent| {
+                // let mesh = Capsule3d::new(0.5, 1.5);
+                _ = parent.spawn(SceneBundle {
+                    scene: ship_assets.ship_001_scene.clone(),
+                });
             });
-        });
     }
 }
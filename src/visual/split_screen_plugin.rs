@@ -0,0 +1,70 @@
+use bevy::{prelude::*, render::camera::Viewport, window::PrimaryWindow};
+
+use crate::game::{ship_plugin::PlayerId, states_plugin::MainState};
+
+#[derive(Debug)]
+pub struct SplitScreenPlugin;
+
+impl Plugin for SplitScreenPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app.add_systems(
+            Update,
+            arrange_viewports.run_if(in_state(MainState::InGame)),
+        );
+    }
+}
+
+/// The camera following a local player's ship.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PlayerCamera(pub PlayerId);
+
+/// Where a player's view goes on a screen of `size` physical pixels shared by `count` players,
+/// side by side for two and in quarters for more.
+fn viewport(index: usize, count: usize, size: UVec2) -> Option<Viewport> {
+    if count <= 1 {
+        return None;
+    }
+
+    let (columns, rows) = if count == 2 { (2, 1) } else { (2, 2) };
+    let cell = UVec2::new(size.x / columns, size.y / rows);
+    let index = u32::try_from(index).ok()?;
+    Some(Viewport {
+        physical_position: UVec2::new(index % columns, index / columns) * cell,
+        physical_size: cell.max(UVec2::ONE),
+        ..default()
+    })
+}
+
+fn same_viewport(a: Option<&Viewport>, b: Option<&Viewport>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            a.physical_position == b.physical_position && a.physical_size == b.physical_size
+        }
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn arrange_viewports(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Camera, &PlayerCamera)>,
+) {
+    let Some(window) = windows.iter().next() else {
+        return;
+    };
+
+    let mut cameras = cameras.iter_mut().collect::<Vec<_>>();
+    cameras.sort_by_key(|(_, PlayerCamera(player))| *player);
+    let count = cameras.len();
+    for (index, (mut camera, _)) in cameras.into_iter().enumerate() {
+        let viewport = viewport(index, count, window.physical_size());
+        if !same_viewport(camera.viewport.as_ref(), viewport.as_ref()) {
+            camera.viewport = viewport;
+        }
+        // Cameras drawing to the same window need distinct orders
+        let order = isize::try_from(index).unwrap_or_default();
+        if camera.order != order {
+            camera.order = order;
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    economy_plugin::{Station, STATION_SIZE},
+    states_plugin::MainState,
+};
+
+#[derive(Debug)]
+pub struct StationPlugin;
+
+impl Plugin for StationPlugin {
+    fn build(&self, app: &mut App) {
+        _ = app
+            .add_systems(OnEnter(MainState::InGame), setup)
+            .add_systems(
+                Update,
+                on_station_created_add_visuals.run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct StationVisuals {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(StationVisuals {
+        mesh: meshes.add(Cuboid::from_length(STATION_SIZE)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.7, 0.75, 0.8),
+            metallic: 0.8,
+            perceptual_roughness: 0.3,
+            ..default()
+        }),
+    });
+}
+
+#[expect(clippy::needless_pass_by_value, reason = "Bevy System syntax")]
+fn on_station_created_add_visuals(
+    mut commands: Commands,
+    visuals: Res<StationVisuals>,
+    query: Query<Entity, Added<Station>>,
+) {
+    for entity in query.iter() {
+        _ = commands
+            .entity(entity)
+            .insert((visuals.mesh.clone(), visuals.material.clone()));
+    }
+}